@@ -0,0 +1,152 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! LobsterError to report problems found while parsing a project.
+
+use std::fmt::Display;
+use std::path::PathBuf;
+
+/// Problems found while reading and parsing the source files of a project.
+///
+/// None of the problems abort the parsing. They are collected by the RustVisitor and can be
+/// reported by the caller after the parsing is finished.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LobsterError {
+    /// A source file could not be read.
+    FileRead {
+        /// Path of the file.
+        path: PathBuf,
+        /// Description of the underlying IO error.
+        message: String,
+    },
+    /// A module declaration (`mod name;`) could not be resolved to a source file.
+    ModuleUnresolved {
+        /// Path of the file declaring the module.
+        path: PathBuf,
+        /// Name of the declared module.
+        module: String,
+    },
+    /// A module declaration resolved via a path attribute to a file outside of the project
+    /// directory. The module is still parsed.
+    ModuleOutsideRoot {
+        /// Path of the file declaring the module.
+        path: PathBuf,
+        /// Name of the declared module.
+        module: String,
+        /// Canonical path of the resolved module file.
+        resolved: PathBuf,
+        /// Canonical path of the project directory.
+        root: PathBuf,
+    },
+    /// A module declaration resolved to a file or directory whose name only differs in casing,
+    /// which fails on case-sensitive file systems. The module is still parsed.
+    ModuleCasing {
+        /// Name of the declared module.
+        module: String,
+        /// Path of the resolved file or directory.
+        resolved: PathBuf,
+    },
+    /// A module declaration resolved to both a file module and a directory module, which rustc
+    /// rejects. The file module is parsed.
+    ModuleAmbiguous {
+        /// Path of the file module, e.g. `sub.rs`.
+        file_module: PathBuf,
+        /// Path of the mod.rs of the directory module, e.g. `sub/mod.rs`.
+        directory_module: PathBuf,
+    },
+    /// An impl block without a valid type to implement for. Its items are still parsed.
+    MalformedImpl {
+        /// Path of the file holding the impl block.
+        path: PathBuf,
+        /// Line of the impl block, 1-based.
+        line: usize,
+        /// Column of the impl block, 1-based.
+        column: usize,
+    },
+    /// A syntax error or an item that could not be parsed.
+    ParseError {
+        /// Path of the file holding the error.
+        path: PathBuf,
+        /// Line of the error, 1-based.
+        line: usize,
+        /// Column of the error, 1-based.
+        column: usize,
+        /// Description of the error.
+        message: String,
+    },
+}
+
+impl Display for LobsterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LobsterError::FileRead { path, message } => {
+                write!(f, "File: {:#?}\n{}", path, message)
+            }
+            LobsterError::ModuleUnresolved { path, module } => write!(
+                f,
+                "Module {} declared in {:#?} could not be resolved. Continuing...",
+                module, path
+            ),
+            LobsterError::ModuleOutsideRoot {
+                path,
+                module,
+                resolved,
+                root,
+            } => write!(
+                f,
+                "Module {} declared in {:#?} resolves to {:#?} outside of the project directory {:#?}. Continuing...",
+                module, path, resolved, root
+            ),
+            LobsterError::ModuleCasing { module, resolved } => write!(
+                f,
+                "Module {} resolved to {:#?} with different casing.",
+                module, resolved
+            ),
+            LobsterError::ModuleAmbiguous {
+                file_module,
+                directory_module,
+            } => write!(
+                f,
+                "Ambiguous module, both {:#?} and {:#?} exist. Using {:#?}.",
+                file_module, directory_module, file_module
+            ),
+            LobsterError::MalformedImpl { path, line, column } => write!(
+                f,
+                "Malformed impl node in {:#?} @{},{}. Continuing...",
+                path, line, column
+            ),
+            LobsterError::ParseError {
+                path,
+                line,
+                column,
+                message,
+            } => write!(f, "{} in {:#?} @{},{}", message, path, line, column),
+        }
+    }
+}
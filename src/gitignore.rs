@@ -0,0 +1,240 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Patterns of a .gitignore file to skip ignored source files.
+//!
+//! The common subset of the gitignore syntax is supported: comments, negated patterns (`!`),
+//! directory patterns (trailing `/`), patterns anchored to the directory of the .gitignore file
+//! (containing a `/`) and the wildcards `*`, `?`, `**` and character classes (`[abc]`).
+//! Only a single .gitignore file is considered, nested .gitignore files are not.
+
+use regex::Regex;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Single pattern of a .gitignore file.
+#[derive(Debug, Clone)]
+struct Rule {
+    /// Regex matching the path relative to the directory of the .gitignore file.
+    regex: Regex,
+    /// The pattern re-includes matching paths (`!pattern`).
+    negated: bool,
+    /// The pattern only matches directories (`pattern/`).
+    dir_only: bool,
+}
+
+/// Patterns loaded from a .gitignore file.
+#[derive(Debug, Clone)]
+pub struct Gitignore {
+    /// Directory of the .gitignore file, the patterns are relative to.
+    base_dir: PathBuf,
+    /// Patterns in the order of the file, later patterns take precedence.
+    rules: Vec<Rule>,
+}
+
+impl Gitignore {
+    /// Load the nearest .gitignore file.
+    ///
+    /// Walks up from the directory to the first directory containing a .gitignore file.
+    ///
+    /// ### Parameters
+    /// * `dir` - Directory to start the search from.
+    ///
+    /// ### Returns
+    /// Some Gitignore if a .gitignore file was found, None otherwise, or the error if the file
+    /// could not be read.
+    pub fn find(dir: &Path) -> io::Result<Option<Self>> {
+        let dir = fs::canonicalize(dir)?;
+        let Some(base_dir) = dir
+            .ancestors()
+            .find(|ancestor| ancestor.join(".gitignore").is_file())
+        else {
+            return Ok(None);
+        };
+        let content = fs::read_to_string(base_dir.join(".gitignore"))?;
+        Ok(Some(Gitignore::parse(&content, base_dir)))
+    }
+
+    /// Parse the content of a .gitignore file.
+    ///
+    /// ### Parameters
+    /// * `content` - Content of the .gitignore file.
+    /// * `base_dir` - Canonical path of the directory of the .gitignore file.
+    ///
+    /// ### Returns
+    /// The parsed Gitignore.
+    pub fn parse(content: &str, base_dir: &Path) -> Self {
+        let rules = content.lines().filter_map(parse_rule).collect();
+        Gitignore {
+            base_dir: base_dir.to_path_buf(),
+            rules,
+        }
+    }
+
+    /// Check if a path is ignored.
+    ///
+    /// A path is ignored if the last pattern matching it is not negated, or if one of its parent
+    /// directories is ignored. Paths outside of the directory of the .gitignore file are never
+    /// ignored.
+    ///
+    /// ### Parameters
+    /// * `path` - Path of the file or directory to check.
+    ///
+    /// ### Returns
+    /// true if the path is ignored.
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let Ok(relative_path) = path.strip_prefix(&self.base_dir) else {
+            return false;
+        };
+        let Some(components) = relative_path
+            .components()
+            .map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+                // Paths leaving the directory, e.g. via `..`, are outside as well.
+                _ => None,
+            })
+            .collect::<Option<Vec<String>>>()
+        else {
+            return false;
+        };
+        // Check the parent directories first, ignored directories can not be re-included from.
+        (1..=components.len()).any(|depth| {
+            let is_dir = depth < components.len() || path.is_dir();
+            self.matches(&components[..depth].join("/"), is_dir)
+        })
+    }
+
+    /// Check if the last pattern matching a relative path ignores it.
+    ///
+    /// ### Parameters
+    /// * `relative_path` - Path relative to the directory of the .gitignore file, separated by
+    ///   forward slashes.
+    /// * `is_dir` - The path is a directory.
+    ///
+    /// ### Returns
+    /// true if the path is ignored by the patterns.
+    fn matches(&self, relative_path: &str, is_dir: bool) -> bool {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| (is_dir || !rule.dir_only) && rule.regex.is_match(relative_path))
+            .is_some_and(|rule| !rule.negated)
+    }
+}
+
+/// Parse a line of a .gitignore file.
+///
+/// ### Parameters
+/// * `line` - Line of the .gitignore file.
+///
+/// ### Returns
+/// Some Rule if the line holds a pattern, None for empty lines and comments.
+fn parse_rule(line: &str) -> Option<Rule> {
+    let line = line.trim_end();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (negated, pattern) = match line.strip_prefix('!') {
+        Some(pattern) => (true, pattern),
+        None => (false, line.strip_prefix('\\').unwrap_or(line)),
+    };
+    let (dir_only, pattern) = match pattern.strip_suffix('/') {
+        Some(pattern) => (true, pattern),
+        None => (false, pattern),
+    };
+    // Patterns with a slash are relative to the .gitignore file, others match at any depth.
+    let anchored = pattern.contains('/');
+    let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+    if pattern.is_empty() {
+        return None;
+    }
+    let prefix = if anchored { "^" } else { "^(?:.*/)?" };
+    let regex = Regex::new(&format!("{}{}$", prefix, glob_to_regex(pattern))).ok()?;
+    Some(Rule {
+        regex,
+        negated,
+        dir_only,
+    })
+}
+
+/// Convert a gitignore glob pattern to a regex.
+///
+/// ### Parameters
+/// * `pattern` - Glob pattern without leading `!` and trailing `/`.
+///
+/// ### Returns
+/// Regex String matching the same paths as the pattern.
+fn glob_to_regex(pattern: &str) -> String {
+    let mut regex = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if Some(&'*') == chars.peek() => {
+                let _ = chars.next();
+                if Some(&'/') == chars.peek() {
+                    // Leading or inner `**/` matches zero or more directories.
+                    let _ = chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    // Trailing `/**` matches everything inside.
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '[' => {
+                let mut class = String::from("[");
+                if Some(&'!') == chars.peek() {
+                    let _ = chars.next();
+                    class.push('^');
+                }
+                for c in chars.by_ref() {
+                    if ']' == c {
+                        break;
+                    }
+                    if '\\' == c || '[' == c {
+                        class.push('\\');
+                    }
+                    class.push(c);
+                }
+                class.push(']');
+                regex.push_str(&class);
+            }
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    regex.push_str(&regex::escape(&escaped.to_string()));
+                }
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex
+}
@@ -0,0 +1,134 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! JUnit XML report of the trace coverage.
+//!
+//! CI test reporters can visualize the annotation coverage of a crate via a JUnit XML report.
+//! Every item that is required to be traced is a test case, named after the item and classed by
+//! its enclosing context. Untraced items are failing test cases, traced items pass.
+
+use std::path::Path;
+
+use crate::location::project_path;
+use crate::traceable_node::{NodeKind, RustTraceableNode};
+
+/// Build a JUnit XML report of the trace coverage.
+///
+/// ### Parameters
+/// * `modules` - Root nodes of the parsed files.
+/// * `kinds` - NodeKinds that are required to be traced.
+/// * `project_root` - Canonical path of the project root the files are reported relative to.
+///
+/// ### Returns
+/// The JUnit XML document, ending with a newline.
+pub fn junit_report(
+    modules: &[RustTraceableNode],
+    kinds: &[NodeKind],
+    project_root: Option<&Path>,
+) -> String {
+    // Items are located in the file of the module they are parsed from.
+    let nodes: Vec<(&RustTraceableNode, String)> = modules
+        .iter()
+        .flat_map(|m| {
+            let file = project_path(&m.location.filename, project_root);
+            m.find_required(kinds)
+                .into_iter()
+                .map(move |node| (node, file.clone()))
+        })
+        .collect();
+    let failures = nodes.iter().filter(|(node, _)| !node.is_traced()).count();
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<testsuites>\n");
+    out.push_str(&format!(
+        "  <testsuite name=\"lobster-rust\" tests=\"{}\" failures=\"{}\">\n",
+        nodes.len(),
+        failures
+    ));
+    for (node, file) in nodes {
+        out.push_str(&test_case(node, &file));
+    }
+    out.push_str("  </testsuite>\n");
+    out.push_str("</testsuites>\n");
+    out
+}
+
+/// Build the test case element of an item.
+///
+/// The classname is the context of the item, e.g. `main.sub`, the name is the item itself, e.g.
+/// `helper`. The file is the path of the source file, e.g. `src/sub.rs`. Untraced items hold a
+/// failure element.
+///
+/// ### Parameters
+/// * `node` - RustTraceableNode of the item.
+/// * `file` - Path of the source file of the item, with `/` separators.
+///
+/// ### Returns
+/// The testcase element, ending with a newline.
+fn test_case(node: &RustTraceableNode, file: &str) -> String {
+    let name = node.name.rsplit('.').next().unwrap_or(&node.name);
+    let attributes = format!(
+        "classname=\"{}\" name=\"{}\" file=\"{}\" line=\"{}\"",
+        escape(&node.scope.to_str()),
+        escape(name),
+        escape(file),
+        node.location.line.unwrap_or(0)
+    );
+    if node.is_traced() {
+        return format!("    <testcase {}/>\n", attributes);
+    }
+    let message = format!(
+        "Untraced {} {} in {} @{},{}",
+        node.kind.to_str(),
+        node.name,
+        node.location.filename,
+        node.location.line.unwrap_or(0),
+        node.location.column.unwrap_or(0)
+    );
+    format!(
+        "    <testcase {}>\n      <failure message=\"{}\"/>\n    </testcase>\n",
+        attributes,
+        escape(&message)
+    )
+}
+
+/// Escape text for XML attribute values.
+///
+/// ### Parameters
+/// * `text` - Text to escape.
+///
+/// ### Returns
+/// The text with `&`, `<`, `>`, `"` and `'` replaced by their entities.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
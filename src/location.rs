@@ -31,16 +31,18 @@
 
 use json::{object::Object, JsonValue};
 use std::fmt::Display;
+use std::fs;
+use std::path::Path;
 
 /// Struct to define the location of an item in a file.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub(crate) struct FileReference {
+pub struct FileReference {
     /// Name of the file.
-    pub(crate) filename: String,
-    /// Line in the file.
-    pub(crate) line: Option<usize>,
-    /// Column in the line.
-    pub(crate) column: Option<usize>,
+    pub filename: String,
+    /// Line in the file, 1-based.
+    pub line: Option<usize>,
+    /// Column in the line, 1-based.
+    pub column: Option<usize>,
 }
 
 impl FileReference {
@@ -86,6 +88,121 @@ impl FileReference {
         self.line = line;
         self.column = column;
     }
+
+    /// Convert the FileReference to a file URI.
+    ///
+    /// Relative filenames are resolved against the current directory first. Windows paths,
+    /// including verbatim paths (`\\?\C:\...`) and UNC paths, are converted to forward slashes.
+    /// Characters that are not allowed in a URI path are percent-encoded. The line is appended as
+    /// fragment, e.g. `file:///home/dev/src/main.rs#L12`, to jump to the item.
+    ///
+    /// ### Returns
+    /// String holding the file URI.
+    pub fn to_file_uri(&self) -> String {
+        let mut path = forward_slashes(&self.filename);
+        if !is_absolute_path(&path) {
+            let absolute =
+                fs::canonicalize(&self.filename).or_else(|_| std::path::absolute(&self.filename));
+            if let Ok(absolute) = absolute {
+                path = forward_slashes(&absolute.display().to_string());
+            }
+        }
+        // Verbatim Windows paths, as returned by fs::canonicalize.
+        let path = match path.strip_prefix("//?/") {
+            Some(verbatim) => match verbatim.strip_prefix("UNC/") {
+                Some(unc) => format!("//{}", unc),
+                None => verbatim.to_string(),
+            },
+            None => path,
+        };
+        let mut uri = if path.starts_with("//") {
+            // UNC path, the server is the authority of the URI.
+            format!("file:{}", percent_encode(&path))
+        } else if path.starts_with('/') {
+            format!("file://{}", percent_encode(&path))
+        } else {
+            // Windows path starting with a drive letter.
+            format!("file:///{}", percent_encode(&path))
+        };
+        if let Some(line) = self.line {
+            uri.push_str(&format!("#L{}", line));
+        }
+        uri
+    }
+}
+
+/// Normalize the separators of a path to forward slashes.
+///
+/// Some consumers of the lobster common interchange format mishandle backslashes, so all emitted
+/// paths use forward slashes, regardless of the platform.
+///
+/// ### Parameters
+/// * `path` - Path with forward slashes or backslashes as separators.
+///
+/// ### Returns
+/// The path with forward slashes as separators.
+pub fn forward_slashes(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+/// Get the path of a source file relative to the project root.
+///
+/// Files outside of the project root keep their path as parsed.
+///
+/// ### Parameters
+/// * `filename` - Path of the source file as parsed.
+/// * `project_root` - Canonical path of the project root, if it exists.
+///
+/// ### Returns
+/// Path of the source file with `/` separators.
+pub fn project_path(filename: &str, project_root: Option<&Path>) -> String {
+    let filename = Path::new(filename);
+    let relative = project_root.and_then(|root| {
+        let path = fs::canonicalize(filename).ok()?;
+        path.strip_prefix(root).ok().map(Path::to_path_buf)
+    });
+    forward_slashes(
+        &relative
+            .as_deref()
+            .unwrap_or(filename)
+            .display()
+            .to_string(),
+    )
+}
+
+/// Check if a path is absolute, on any platform.
+///
+/// Paths starting with a separator, including UNC and verbatim Windows paths, and Windows paths
+/// starting with a drive letter are absolute.
+///
+/// ### Parameters
+/// * `path` - Path with forward slashes as separators.
+///
+/// ### Returns
+/// True if the path is absolute, false otherwise.
+fn is_absolute_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    path.starts_with('/')
+        || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+/// Percent-encode a path for use in a URI.
+///
+/// ### Parameters
+/// * `path` - Path with forward slashes as separators.
+///
+/// ### Returns
+/// The path with all bytes that are not allowed in a URI path percent-encoded.
+fn percent_encode(path: &str) -> String {
+    path.bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() || b"-._~/:@!$&'()*+,;=".contains(&byte) {
+                (byte as char).to_string()
+            } else {
+                format!("%{:02X}", byte)
+            }
+        })
+        .collect()
 }
 
 /// Implement JsonValue::from(node: &FileReference)
@@ -98,7 +215,7 @@ impl From<&FileReference> for JsonValue {
     /// This conversion returns json in the form of a location object in the lobster common
     /// interchange format. This conversion is needed when converting a RustTraceableNode to
     /// lobster, as the node will contain a FileReference. The relevant fields of the location
-    /// are parsed to the corresponding json fields.
+    /// are parsed to the corresponding json fields. The file is emitted with forward slashes.
     ///
     /// ### Parameters
     /// * `reference` - FileReference to convert to JsonValue.
@@ -107,7 +224,7 @@ impl From<&FileReference> for JsonValue {
     fn from(reference: &FileReference) -> Self {
         let mut location_json = JsonValue::Object(Object::new());
         let _ = location_json.insert("kind", "file");
-        let _ = location_json.insert("file", reference.filename.clone());
+        let _ = location_json.insert("file", forward_slashes(&reference.filename));
         let _ = location_json.insert("line", reference.line);
         let _ = location_json.insert("column", reference.column);
         location_json
@@ -31,17 +31,34 @@
 
 use clap::Parser;
 use json::{object::Object, JsonValue};
-use std::fs::File;
-use std::io::BufWriter;
-use std::path::Path;
-use utils::context::Context;
-use visitor::RustVisitor;
-
-mod location;
-mod syntax_extensions;
-mod traceable_node;
-mod utils;
-mod visitor;
+use lobster_rust::gitignore::Gitignore;
+use lobster_rust::junit;
+use lobster_rust::location::{forward_slashes, project_path};
+use lobster_rust::manifest::CrateInfo;
+use lobster_rust::output::{self, write_document};
+use lobster_rust::requirements::{self, Requirements};
+use lobster_rust::roots;
+use lobster_rust::schema;
+use lobster_rust::sidecar::Sidecar;
+use lobster_rust::trace_diff::{self, TraceDiff};
+use lobster_rust::traceable_node::{NodeKind, OutputOptions, RustTraceableNode};
+use lobster_rust::trait_refs;
+use lobster_rust::utils::context::Context;
+use lobster_rust::validation;
+use lobster_rust::visitor::{
+    self, RustVisitor, ThreadBudget, VisitorOptions, PARSER_EDITION, RA_AP_SYNTAX_VERSION,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufWriter};
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 /// Entry function of the tool.
 ///
@@ -53,68 +70,1415 @@ fn main() {
     // Parse command line interface arguments.
     let args = args::Cli::parse();
 
-    // Determine entry file filename (lib.rs instead of main.rs if --lib flag is set).
+    // Only print the output schema if requested.
+    if args.print_schema {
+        println!("{}", schema::lobster_schema().pretty(4));
+        return;
+    }
+
+    // Only print the version details for bug reports if requested.
+    if args.version_info {
+        println!("lobster-rust: {}", env!("CARGO_PKG_VERSION"));
+        println!("ra_ap_syntax: {}", RA_AP_SYNTAX_VERSION);
+        let schema = if args.activity {
+            ACT_SCHEMA
+        } else {
+            IMP_SCHEMA
+        };
+        println!("edition: {}", PARSER_EDITION);
+        println!("schema: {}", schema.0);
+        println!("schema_version: {}", schema.1);
+        return;
+    }
+
+    // Run once, or on every change of the sources if requested.
+    if args.watch {
+        watch(&args);
+    } else if !run(&args, &mut Vec::new()) {
+        std::process::exit(1);
+    }
+}
+
+/// Run the parse pipeline once and write the output.
+///
+/// Parses the crate roots given by the CLI args, applies all requested checks and conversions and
+/// writes the lobster common interchange format output.
+///
+/// ### Parameters
+/// * `args` - Parsed command line interface arguments.
+/// * `parsed_files` - The paths of all parsed source files and of the roots manifest are appended
+///   to it, e.g. to watch them for changes.
+///
+/// ### Returns
+/// false if the coverage gate found untraced items or strict reference validation failed, true
+/// otherwise.
+fn run(args: &args::Cli, parsed_files: &mut Vec<PathBuf>) -> bool {
+    // Determine the source directory and the entry file.
+    let (dir, filename) = entry_point(args);
+    let filepath = dir.join(filename);
+
+    // Parse entry file and other modules in the project.
+    let threads = args
+        .threads
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get);
+    let gitignore = if args.respect_gitignore {
+        match Gitignore::find(dir) {
+            Err(e) => panic!("Gitignore: {:#?}\n{}", dir, e),
+            Ok(None) => {
+                eprintln!("WARNING: No .gitignore found for {:#?}.", dir);
+                None
+            }
+            Ok(gitignore) => gitignore.map(Arc::new),
+        }
+    } else {
+        None
+    };
+    let visitor_options = VisitorOptions {
+        case_insensitive_modules: args.case_insensitive_modules,
+        root_name: args.root_name.clone(),
+        threads: ThreadBudget::new(threads),
+        gitignore: gitignore.clone(),
+        locate_at: args.locate_at.to_locate_at(),
+        skip_modules: args.input_list.is_some(),
+        activity: args.activity,
+        test_attributes: args.test_attrs.clone(),
+        strip_prefix: args.strip_prefix.clone(),
+        project_root: visitor::project_root(dir),
+        parse_macro_bodies: args.parse_macro_bodies,
+        file_as_context: args.file_as_context,
+        trace_enums: args.trace_enums,
+        cfg_filter: args.cfg_filter,
+        modules_only: args.list_files,
+        extensions: Vec::new(),
+    };
+    let mut modules = match &args.input_list {
+        // Parse exactly the listed files instead of following module declarations.
+        Some(input_list) => {
+            parse_input_list(Path::new(input_list), dir, filename, &visitor_options)
+        }
+        // The roots manifest replaces the entry file in DIR if requested.
+        None if args.roots_only => Vec::new(),
+        None => parse_crate_root(filepath, visitor_options.clone()),
+    };
+
+    // Parse additional crate roots, named after the binary or the build script.
+    let bin_dir = dir.join("bin");
+    let mut bins: Vec<(String, PathBuf)> = args
+        .bin
+        .iter()
+        .map(|name| (name.clone(), bin_path(&bin_dir, name)))
+        .collect();
+    if args.all_bins {
+        for bin in discover_bins(&bin_dir, gitignore.as_deref()) {
+            if !bins.contains(&bin) {
+                bins.push(bin);
+            }
+        }
+    }
+    if args.build_script {
+        let build_path = dir.join("..").join("build.rs");
+        bins.push(("build".to_string(), build_path));
+    }
+    for (name, path) in bins {
+        let root_options = VisitorOptions {
+            root_name: Some(name),
+            ..visitor_options.clone()
+        };
+        modules.append(&mut parse_crate_root(path, root_options));
+    }
+
+    // Parse the crate roots of the roots manifest, named after their context.
+    if let Some(roots_path) = &args.roots {
+        let roots = match roots::load_roots(Path::new(roots_path)) {
+            Err(e) => panic!("Roots: {:#?}\n{}", roots_path, e),
+            Ok((roots, warnings)) => {
+                for warning in warnings {
+                    eprintln!("WARNING: {}", warning);
+                }
+                roots
+            }
+        };
+        for root in roots {
+            let root_options = VisitorOptions {
+                root_name: Some(root.context),
+                ..visitor_options.clone()
+            };
+            modules.append(&mut parse_crate_root(root.path, root_options));
+        }
+        parsed_files.push(PathBuf::from(roots_path));
+    }
+    parsed_files.extend(
+        modules
+            .iter()
+            .map(|module| PathBuf::from(&module.location.filename)),
+    );
+
+    // Only list the parsed files if requested.
+    if args.list_files {
+        for module in modules.iter() {
+            println!("{}", forward_slashes(&module.location.filename));
+        }
+        return true;
+    }
+
+    // Let trait implementations inherit the references of the trait methods across all files.
+    trait_refs::link_trait_method_refs(&mut modules);
+
+    // Attach references from the sidecar file.
+    if let Some(sidecar_path) = &args.sidecar {
+        match Sidecar::from_file(Path::new(sidecar_path)) {
+            Err(e) => panic!("Sidecar: {:#?}\n{}", sidecar_path, e),
+            Ok((sidecar, warnings)) => {
+                for warning in warnings {
+                    eprintln!("WARNING: {}", warning);
+                }
+                modules.iter_mut().for_each(|m| sidecar.apply(m));
+            }
+        }
+    }
+
+    // Options of the conversion to the lobster common interchange format.
+    let options = OutputOptions {
+        tag_prefix: args.tag_prefix.clone(),
+        zero_based_columns: args.zero_based_columns,
+        // Without context, names keep none of the enclosing namespaces.
+        max_context_depth: if args.no_context {
+            Some(0)
+        } else {
+            args.max_context_depth
+        },
+        ref_filter: args.ref_filter.clone(),
+        requirement_kinds: args
+            .as_requirements
+            .iter()
+            .map(|kind| kind.to_node_kind())
+            .collect(),
+        file_uri: args.file_uri,
+        kind_map: args.kind_map.clone().unwrap_or_default(),
+        complexity: args.complexity,
+        skip_kinds: args
+            .skip_kinds
+            .iter()
+            .map(|kind| kind.to_node_kind())
+            .collect(),
+    };
+    // Activity traces hold the test functions instead of the implementation items.
+    let (to_items, schema): (fn(&RustTraceableNode, &OutputOptions) -> Vec<JsonValue>, _) =
+        if args.activity {
+            (RustTraceableNode::to_lobster_activities, ACT_SCHEMA)
+        } else {
+            (RustTraceableNode::to_lobster, IMP_SCHEMA)
+        };
+
+    // Only print the number of items referencing each requirement if requested.
+    if args.ref_histogram {
+        let requirements = args.requirements.as_ref().map(|requirements_path| {
+            match Requirements::from_file(Path::new(requirements_path)) {
+                Err(e) => panic!("Requirements: {:#?}\n{}", requirements_path, e),
+                Ok(requirements) => requirements,
+            }
+        });
+        // Only the items that are written count, after all output filters.
+        let items: Vec<JsonValue> = modules.iter().flat_map(|m| to_items(m, &options)).collect();
+        for (id, count) in requirements::ref_histogram(&items, requirements.as_ref()) {
+            println!("{}\t{}", count, id);
+        }
+        return true;
+    }
+
+    // Locate all items in the path of their source file relative to the git repository root.
+    if args.relative_to_git_root {
+        let git_root = find_git_root(dir);
+        if git_root.is_none() {
+            eprintln!(
+                "WARNING: No git repository found for {:#?}, emitting paths as given.",
+                dir
+            );
+        }
+        for module in modules.iter_mut() {
+            let source_path = PathBuf::from(&module.location.filename);
+            let filename = git_root
+                .as_deref()
+                .and_then(|root| relative_path(&source_path, root))
+                .unwrap_or_else(|| source_path.display().to_string());
+            module.set_filename(&filename);
+        }
+    }
+
+    // Locate all items in the absolute path of their source file, to be emitted as file URI.
+    if args.file_uri {
+        for module in modules.iter_mut() {
+            match fs::canonicalize(&module.location.filename) {
+                Ok(source_path) => module.set_filename(&source_path.display().to_string()),
+                Err(e) => eprintln!("WARNING: File: {:#?}\n{}", module.location.filename, e),
+            }
+        }
+    }
+
+    // Collect items that violate the coverage gate before the output is written.
+    let required_kinds: Vec<NodeKind> = args
+        .require_trace_on
+        .iter()
+        .map(|kind| kind.to_node_kind())
+        .collect();
+    let mut errors: Vec<String> = if args.fail_on_untraced {
+        modules
+            .iter()
+            .flat_map(|m| m.find_untraced(&required_kinds))
+            .map(|node| {
+                format!(
+                    "ERROR: Untraced {} {} in {} @{},{}",
+                    node.kind.to_str(),
+                    node.name,
+                    node.location.filename,
+                    node.location.line.unwrap_or(0),
+                    node.location.column.unwrap_or(0)
+                )
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // Report the coverage of the required kinds for CI test reporters.
+    if let Some(junit_path) = &args.junit {
+        if let Err(e) = fs::write(
+            junit_path,
+            junit::junit_report(
+                &modules,
+                &required_kinds,
+                visitor_options.project_root.as_deref(),
+            ),
+        ) {
+            panic!("JUnit report: {:#?}\n{}", junit_path, e);
+        }
+    }
+
+    // Validate references against the list of valid requirements.
+    if let Some(requirements_path) = &args.requirements {
+        let requirements = match Requirements::from_file(Path::new(requirements_path)) {
+            Err(e) => panic!("Requirements: {:#?}\n{}", requirements_path, e),
+            Ok(requirements) => requirements,
+        };
+        let severity = if args.strict_refs { "ERROR" } else { "WARNING" };
+        for (node, reference) in modules.iter().flat_map(|m| requirements.find_unknown(m)) {
+            let message = format!(
+                "{}: Unknown requirement {} referenced by {} {} in {} @{},{}",
+                severity,
+                reference,
+                node.kind.to_str(),
+                node.name,
+                node.location.filename,
+                node.location.line.unwrap_or(0),
+                node.location.column.unwrap_or(0)
+            );
+            if args.strict_refs {
+                errors.push(message);
+            } else {
+                eprintln!("{}", message);
+            }
+        }
+    }
+
+    let schema_ref = args.schema_ref.as_deref();
+    let project_root = visitor_options.project_root.clone();
+    let files = source_files(&modules, project_root.as_deref());
+
+    // Identify the crate via its manifest, given explicitly or found next to DIR.
+    let crate_info = match &args.manifest {
+        Some(manifest_path) => match CrateInfo::from_file(Path::new(manifest_path)) {
+            Err(e) => panic!("Manifest: {:#?}\n{}", manifest_path, e),
+            Ok(crate_info) => crate_info,
+        },
+        None => CrateInfo::from_file(&dir.join("..").join("Cargo.toml"))
+            .ok()
+            .flatten(),
+    };
+    let metadata = DocumentMetadata {
+        schema_ref,
+        crate_info: crate_info.as_ref(),
+        files: args.emit_files.then_some(files.as_slice()),
+    };
+
+    // Convert parsed modules to lobster common interchange format.
+    let mut module_items: Vec<Vec<JsonValue>> =
+        modules.iter().map(|m| to_items(m, &options)).collect();
+
+    // Bare names of items in different namespaces may collide.
+    if args.no_context {
+        let module_files: Vec<String> = modules
+            .iter()
+            .map(|module| project_path(&module.location.filename, project_root.as_deref()))
+            .collect();
+        disambiguate_tags(
+            module_files
+                .iter()
+                .zip(module_items.iter_mut())
+                .flat_map(|(file, items)| items.iter_mut().map(move |item| (file.as_str(), item)))
+                .collect(),
+        );
+    }
+
+    // Items without location are useless in the report, e.g. after a location tracking regression.
+    for warning in validation::unlocated_item_warnings(module_items.iter().flatten()) {
+        eprintln!("{}", warning);
+    }
+
+    if let Some(split_dir) = &args.split_output {
+        // Write one output file per source file, named after the file.
+        if let Err(e) = fs::create_dir_all(split_dir) {
+            panic!("Output directory: {:#?}\n{}", split_dir, e);
+        }
+        let mut used_names: HashMap<String, usize> = HashMap::new();
+        for (module, items) in modules.iter().zip(module_items.iter()) {
+            let count = used_names.entry(module.name.clone()).or_insert(0);
+            *count += 1;
+            let filename = if 1 == *count {
+                format!("{}.lobster", module.name)
+            } else {
+                eprintln!(
+                    "WARNING: Multiple source files named {}, numbering the output files.",
+                    module.name
+                );
+                format!("{}_{}.lobster", module.name, count)
+            };
+            let module_files = source_files(std::slice::from_ref(module), project_root.as_deref());
+            let module_metadata = DocumentMetadata {
+                files: metadata.files.map(|_| module_files.as_slice()),
+                ..metadata
+            };
+            let jout = lobster_document(items.clone(), schema, &module_metadata);
+            write_lobster(&Path::new(split_dir).join(filename), &jout, args.format);
+        }
+    } else {
+        let data: Vec<JsonValue> = module_items.iter().flatten().cloned().collect();
+        let jout = lobster_document(data, schema, &metadata);
+        match &args.append {
+            // Merge into the existing document, if any, e.g. when running per crate in a loop.
+            Some(append_path) => {
+                let jout = if Path::new(append_path).exists() {
+                    match output::load_document(Path::new(append_path))
+                        .and_then(|existing| output::merge_documents(&existing, jout))
+                    {
+                        Err(e) => panic!("Append: {:#?}\n{}", append_path, e),
+                        Ok((merged, conflicts)) => {
+                            for conflict in conflicts {
+                                eprintln!("WARNING: {}", conflict);
+                            }
+                            merged
+                        }
+                    }
+                } else {
+                    jout
+                };
+                write_lobster(Path::new(append_path), &jout, args.format);
+            }
+            None => write_lobster(Path::new(&args.out), &jout, args.format),
+        }
+    }
+
+    // Compare the output with a previous trace, to review the tracing impact of a change.
+    if let Some(diff_path) = &args.diff {
+        let old_trace = match trace_diff::load_trace(Path::new(diff_path)) {
+            Err(e) => panic!("Diff: {:#?}\n{}", diff_path, e),
+            Ok(old_trace) => old_trace,
+        };
+        let new_trace = trace_diff::trace_refs(module_items.iter().flatten());
+        let report = TraceDiff::new(&old_trace, &new_trace).to_string();
+        match &args.diff_out {
+            Some(diff_out) => {
+                if let Err(e) = fs::write(diff_out, report + "\n") {
+                    panic!("Diff report: {:#?}\n{}", diff_out, e);
+                }
+            }
+            None => eprintln!("{}", report),
+        }
+    }
+
+    // Write the items emitted as requirements to their own document.
+    if !options.requirement_kinds.is_empty() {
+        let data: Vec<JsonValue> = modules
+            .iter()
+            .flat_map(|m| m.to_lobster_requirements(&options))
+            .collect();
+        write_lobster(
+            Path::new(&args.requirements_out),
+            &lobster_document(
+                data,
+                REQ_SCHEMA,
+                &DocumentMetadata {
+                    schema_ref: None,
+                    ..metadata
+                },
+            ),
+            args.format,
+        );
+    }
+
+    // Report items that failed the coverage gate or strict reference validation.
+    errors.iter().for_each(|message| eprintln!("{}", message));
+    errors.is_empty()
+}
+
+/// Make the tags of items unique by appending the location to duplicate tags.
+///
+/// Items whose tag is shared with another item get the path of their source file relative to the
+/// project root and their line appended to the tag and the name, e.g. `rust helper@src/sub.rs:3`.
+/// Items with unique tags are kept unchanged. Parent tags referring to a renamed item are renamed
+/// as well. The parent of an item is searched in the same source file, if the parent tag is shared
+/// by several items in the file, the nearest one before the item is its parent.
+///
+/// ### Parameters
+/// * `items` - Items in the lobster common interchange format, with the path of their source file.
+fn disambiguate_tags(mut items: Vec<(&str, &mut JsonValue)>) {
+    let mut tag_counts: HashMap<String, usize> = HashMap::new();
+    for (_, item) in items.iter() {
+        if let Some(tag) = item["tag"].as_str() {
+            *tag_counts.entry(tag.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    // Collect the new tags of duplicate tags by file, with the lines of the renamed items.
+    let mut renamed: HashMap<(String, String), Vec<(usize, String)>> = HashMap::new();
+    for (file, item) in items.iter() {
+        let Some(tag) = item["tag"]
+            .as_str()
+            .filter(|tag| tag_counts.get(*tag).is_some_and(|count| *count > 1))
+        else {
+            continue;
+        };
+        let line = item["location"]["line"].as_usize().unwrap_or(0);
+        renamed
+            .entry((file.to_string(), tag.to_string()))
+            .or_default()
+            .push((line, format!("{}@{}:{}", tag, file, line)));
+    }
+
+    for (file, item) in items.iter_mut() {
+        let line = item["location"]["line"].as_usize().unwrap_or(0);
+        let parent_tag = item["parent_tag"]
+            .as_str()
+            .and_then(|parent| renamed.get(&(file.to_string(), parent.to_string())))
+            .and_then(|parents| {
+                parents
+                    .iter()
+                    .rev()
+                    .find(|(parent_line, _)| *parent_line <= line)
+                    .or(parents.first())
+            })
+            .map(|(_, parent_tag)| parent_tag.clone());
+        if let Some(parent_tag) = parent_tag {
+            item["parent_tag"] = JsonValue::String(parent_tag);
+        }
+        let duplicate = item["tag"]
+            .as_str()
+            .is_some_and(|tag| tag_counts.get(tag).is_some_and(|count| *count > 1));
+        if !duplicate {
+            continue;
+        }
+        let suffix = format!("@{}:{}", file, line);
+        for key in ["tag", "name"] {
+            let value = format!("{}{}", item[key].as_str().unwrap_or_default(), suffix);
+            item[key] = JsonValue::String(value);
+        }
+    }
+}
+
+/// Interval in which the sources are checked for changes in watch mode.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Time the sources have to be unchanged before the output is regenerated in watch mode.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Age after which the modification time of a file is trusted in watch mode.
+///
+/// Edits within the resolution of the file system timestamps (up to 2 seconds, e.g. on FAT) do
+/// not change the modification time, younger files are therefore compared by content.
+const WATCH_MTIME_RESOLUTION: Duration = Duration::from_secs(2);
+
+/// State of a file watched for changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FileState {
+    /// Modification time and size of the file, None if the metadata can not be read.
+    metadata: Option<(SystemTime, u64)>,
+    /// Hash of the content of the file, None if it can not be read.
+    hash: Option<u64>,
+}
+
+/// Get the source directory and the filename of the entry file.
+///
+/// DIR is the directory of main.rs, or of lib.rs if --lib is set. If DIR is a file, it is the
+/// entry file itself, e.g. to trace a single file, and its directory is the source directory.
+///
+/// ### Parameters
+/// * `args` - Parsed command line interface arguments.
+///
+/// ### Returns
+/// Tuple of the source directory and the filename of the entry file.
+fn entry_point(args: &args::Cli) -> (&Path, &Path) {
+    let dir = Path::new(&args.dir);
+    if dir.is_file() {
+        let filename = dir.file_name().map_or(dir, Path::new);
+        let parent = dir
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        return (parent, filename);
+    }
     let filename = if args.lib {
         Path::new("lib.rs")
     } else {
         Path::new("main.rs")
     };
-    let filepath = Path::new(&args.dir).join(filename);
+    (dir, filename)
+}
+
+/// Run the parse pipeline on every change of the sources.
+///
+/// The output is generated once initially. Afterwards the .rs files in the source directory and
+/// all other files of the last run, e.g. the roots manifest, roots outside of the source directory
+/// or files included via `include!`, are polled for changes (see source_snapshot). The full
+/// pipeline is re-run once the files stopped changing for a moment, so that a burst of writes
+/// (e.g. saving several files) results in a single regeneration. A one-line status is printed per
+/// regeneration. A run that panics, e.g. on an unreadable manifest, is reported as failed and the
+/// loop continues. The loop runs until the process is interrupted (Ctrl-C).
+///
+/// ### Parameters
+/// * `args` - Parsed command line interface arguments.
+fn watch(args: &args::Cli) {
+    let (dir, _) = entry_point(args);
+    let gitignore = Gitignore::find(dir).ok().flatten();
+    let snapshot_of = |files: &[PathBuf], previous: &BTreeMap<PathBuf, FileState>| {
+        source_snapshot(dir, files, gitignore.as_ref(), previous)
+    };
+    let mut parsed_files = Vec::new();
+    let mut snapshot = snapshot_of(&parsed_files, &BTreeMap::new());
+    run_caught(args, &mut parsed_files);
+    let refreshed = snapshot_of(&parsed_files, &snapshot);
+    snapshot = extend_snapshot(snapshot, refreshed);
+    eprintln!("Watching {:#?} for changes, press Ctrl-C to exit.", dir);
+    loop {
+        thread::sleep(WATCH_INTERVAL);
+        let mut current = snapshot_of(&parsed_files, &snapshot);
+        if 0 == changed_files(&snapshot, &current) {
+            // Keep the metadata, so that unchanged files are not read again.
+            snapshot = current;
+            continue;
+        }
+        // Debounce until the sources stopped changing.
+        loop {
+            thread::sleep(WATCH_DEBOUNCE);
+            let settled = snapshot_of(&parsed_files, &current);
+            if 0 == changed_files(&current, &settled) {
+                break;
+            }
+            current = settled;
+        }
+        let changed = changed_files(&snapshot, &current);
+        snapshot = current;
+        parsed_files.clear();
+        let status = match run_caught(args, &mut parsed_files) {
+            Some(true) => "ok",
+            Some(false) => "failed",
+            None => "failed (panic)",
+        };
+        let refreshed = snapshot_of(&parsed_files, &snapshot);
+        snapshot = extend_snapshot(snapshot, refreshed);
+        eprintln!(
+            "Regenerated output after {} changed file(s): {}",
+            changed, status
+        );
+    }
+}
 
-    // Create and run visitor on entry file.
-    let mut visitor = RustVisitor::new(filepath, Context::Empty);
+/// Run the parse pipeline once, catching panics.
+///
+/// ### Parameters
+/// * `args` - Parsed command line interface arguments.
+/// * `parsed_files` - The paths of the parsed files are appended to it (see run).
+///
+/// ### Returns
+/// Some result of the run, None if the run panicked.
+fn run_caught(args: &args::Cli, parsed_files: &mut Vec<PathBuf>) -> Option<bool> {
+    panic::catch_unwind(AssertUnwindSafe(|| run(args, parsed_files))).ok()
+}
+
+/// Take a snapshot of the .rs files in a directory and its subdirectories and of further files.
+///
+/// Files are compared by content, as the modification time may not change for edits within the
+/// resolution of the file system timestamps. To avoid reading every file on every poll, the
+/// content hash of the previous snapshot is reused for files whose modification time and size are
+/// unchanged and whose last modification is older than the timestamp resolution. Hidden
+/// directories, e.g. .git, and directories ignored by the .gitignore, e.g. target, are skipped.
+///
+/// ### Parameters
+/// * `dir` - Directory to take the snapshot of.
+/// * `files` - Further files to take the snapshot of, e.g. files outside of the directory.
+/// * `gitignore` - Optional Gitignore, ignored directories are skipped.
+/// * `previous` - Previous snapshot, whose hashes are reused for unmodified files.
+///
+/// ### Returns
+/// Map of the paths of all files to their state.
+fn source_snapshot(
+    dir: &Path,
+    files: &[PathBuf],
+    gitignore: Option<&Gitignore>,
+    previous: &BTreeMap<PathBuf, FileState>,
+) -> BTreeMap<PathBuf, FileState> {
+    let file_state = |path: &Path| {
+        let metadata = fs::metadata(path)
+            .ok()
+            .and_then(|metadata| Some((metadata.modified().ok()?, metadata.len())));
+        let settled = metadata.is_some_and(|(modified, _)| {
+            modified
+                .elapsed()
+                .is_ok_and(|age| age > WATCH_MTIME_RESOLUTION)
+        });
+        if let Some(state) = previous
+            .get(path)
+            .filter(|state| settled && state.metadata == metadata)
+        {
+            return *state;
+        }
+        let hash = fs::read(path).ok().map(|content| {
+            let mut hasher = DefaultHasher::new();
+            content.hash(&mut hasher);
+            hasher.finish()
+        });
+        FileState { metadata, hash }
+    };
+    let mut snapshot = BTreeMap::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+            if path.is_dir() {
+                let hidden = path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+                if !hidden && !gitignore.is_some_and(|gitignore| gitignore.is_ignored(&path)) {
+                    dirs.push(path);
+                }
+            } else if path.extension().is_some_and(|ext| "rs" == ext) {
+                let state = file_state(&path);
+                let _ = snapshot.insert(path, state);
+            }
+        }
+    }
+    for path in files {
+        let _ = snapshot.insert(path.clone(), file_state(path));
+    }
+    snapshot
+}
+
+/// Count the files whose content differs between two snapshots.
+///
+/// ### Parameters
+/// * `before` - Earlier snapshot.
+/// * `after` - Later snapshot.
+///
+/// ### Returns
+/// Number of files that were changed, added or removed.
+fn changed_files(
+    before: &BTreeMap<PathBuf, FileState>,
+    after: &BTreeMap<PathBuf, FileState>,
+) -> usize {
+    after
+        .iter()
+        .filter(|(path, state)| before.get(*path).map(|before| before.hash) != Some(state.hash))
+        .count()
+        + before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .count()
+}
+
+/// Extend a snapshot taken before a run by the files found during the run.
+///
+/// Files of the snapshot keep their state from before the run, so that changes during the run
+/// trigger another run. Files no longer present are dropped.
+///
+/// ### Parameters
+/// * `snapshot` - Snapshot taken before the run.
+/// * `refreshed` - Snapshot of the files of the run, taken after the run.
+///
+/// ### Returns
+/// The extended snapshot.
+fn extend_snapshot(
+    snapshot: BTreeMap<PathBuf, FileState>,
+    refreshed: BTreeMap<PathBuf, FileState>,
+) -> BTreeMap<PathBuf, FileState> {
+    refreshed
+        .into_iter()
+        .map(|(path, state)| {
+            let state = snapshot.get(&path).copied().unwrap_or(state);
+            (path, state)
+        })
+        .collect()
+}
+
+/// Parse a crate root and all modules included by it.
+///
+/// Problems found while parsing are printed as warnings.
+///
+/// ### Parameters
+/// * `filepath` - Path of the crate root file, e.g. main.rs.
+/// * `options` - VisitorOptions to configure the parsing.
+///
+/// ### Returns
+/// The root nodes of the crate root file and of all included files.
+fn parse_crate_root(filepath: PathBuf, options: VisitorOptions) -> Vec<RustTraceableNode> {
+    parse_with(RustVisitor::new(filepath, Context::Empty, options))
+}
+
+/// Parse a file with the given visitor.
+///
+/// Problems found while parsing are printed as warnings.
+///
+/// ### Parameters
+/// * `visitor` - RustVisitor for the file to parse.
+///
+/// ### Returns
+/// The root nodes of the parsed files.
+fn parse_with(mut visitor: RustVisitor) -> Vec<RustTraceableNode> {
     visitor.parse_file();
+    for error in visitor.errors() {
+        eprintln!("WARNING: {}", error);
+    }
+    visitor.get_traceable_nodes()
+}
 
-    // Get root node of entry file and other modules in the project.
-    let modules = visitor.get_traceable_nodes();
+/// Parse the files of an input list.
+///
+/// The input list holds one path per line, empty lines and lines starting with `#` are ignored.
+/// Every listed file is parsed on its own, module declarations are not followed. The context of
+/// a file is derived from its path relative to DIR, like for a declared module, e.g. `main.sub`
+/// for `sub/nested.rs`. The entry file (main.rs or lib.rs) in DIR is parsed as crate root.
+///
+/// ### Parameters
+/// * `list_path` - Path of the input list.
+/// * `dir` - DIR the context of the files is derived from.
+/// * `entry_filename` - Filename of the entry file, main.rs or lib.rs.
+/// * `options` - VisitorOptions to configure the parsing.
+///
+/// ### Returns
+/// The root nodes of the listed files, in the order of the list.
+fn parse_input_list(
+    list_path: &Path,
+    dir: &Path,
+    entry_filename: &Path,
+    options: &VisitorOptions,
+) -> Vec<RustTraceableNode> {
+    let content = match fs::read_to_string(list_path) {
+        Err(e) => panic!("Input list: {:#?}\n{}", list_path, e),
+        Ok(content) => content,
+    };
+    let base = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    let root_context = options.root_name.clone().unwrap_or_else(|| {
+        entry_filename
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default()
+    });
 
-    // Convert parsed modules to lobster common interchange format.
-    let data: Vec<JsonValue> = modules.iter().flat_map(|m| m.to_lobster()).collect();
+    let mut modules = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let path = PathBuf::from(line);
+        let relative = relative_path(&path, &base).unwrap_or_else(|| {
+            eprintln!(
+                "WARNING: Listed file {:#?} is not located in {:#?}.",
+                line, dir
+            );
+            path.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+        let visitor = if Path::new(&relative) == entry_filename {
+            RustVisitor::new(path, Context::Empty, options.clone())
+        } else {
+            // The directories of the file are nested in the root context.
+            let mut components: Vec<&str> = relative.split('/').collect();
+            let _ = components.pop();
+            let context = components.into_iter().fold(
+                &Context::Empty + root_context.clone(),
+                |context, component| &context + component.to_string(),
+            );
+            RustVisitor::new_module(path, context, options.clone())
+        };
+        modules.append(&mut parse_with(visitor));
+    }
+    modules
+}
+
+/// Get the path of the crate root of a binary.
+///
+/// Binaries are either a single file `src/bin/<name>.rs` or a directory with a main file
+/// `src/bin/<name>/main.rs`.
+///
+/// ### Parameters
+/// * `bin_dir` - Path of the src/bin directory.
+/// * `name` - Name of the binary.
+///
+/// ### Returns
+/// Path of the crate root file of the binary.
+fn bin_path(bin_dir: &Path, name: &str) -> PathBuf {
+    let file_path = bin_dir.join(format!("{}.rs", name));
+    let main_path = bin_dir.join(name).join("main.rs");
+    if !file_path.is_file() && main_path.is_file() {
+        main_path
+    } else {
+        file_path
+    }
+}
+
+/// Find the root of the git repository enclosing a directory.
+///
+/// Walks up from the directory to the first directory containing a `.git` directory or file.
+///
+/// ### Parameters
+/// * `dir` - Directory to start the search from.
+///
+/// ### Returns
+/// The canonical path of the repository root, None if the directory is not in a git repository.
+fn find_git_root(dir: &Path) -> Option<PathBuf> {
+    let dir = fs::canonicalize(dir).ok()?;
+    dir.ancestors()
+        .find(|ancestor| ancestor.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Get the path of a file relative to a directory, separated by forward slashes.
+///
+/// ### Parameters
+/// * `path` - Path of the file.
+/// * `base` - Canonical path of the directory.
+///
+/// ### Returns
+/// The relative path, None if the file does not exist or is not located in the directory.
+fn relative_path(path: &Path, base: &Path) -> Option<String> {
+    let path = fs::canonicalize(path).ok()?;
+    let components: Vec<String> = path
+        .strip_prefix(base)
+        .ok()?
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect();
+    Some(components.join("/"))
+}
 
-    // Combine parsed data and fixed information to full lobster common interchange format output.
+/// Discover all binaries in the src/bin directory.
+///
+/// ### Parameters
+/// * `bin_dir` - Path of the src/bin directory.
+/// * `gitignore` - Optional Gitignore, ignored binaries are skipped.
+///
+/// ### Returns
+/// Names and crate root paths of the binaries, sorted by name.
+fn discover_bins(bin_dir: &Path, gitignore: Option<&Gitignore>) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(bin_dir) else {
+        eprintln!("WARNING: No binaries found in {:#?}", bin_dir);
+        return Vec::new();
+    };
+    let mut bins: Vec<(String, PathBuf)> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| !gitignore.is_some_and(|gitignore| gitignore.is_ignored(path)))
+        .filter_map(|path| {
+            if path.is_file() && path.extension().is_some_and(|ext| "rs" == ext) {
+                let name = path.file_stem()?.to_string_lossy().to_string();
+                Some((name, path))
+            } else if path.is_dir() && path.join("main.rs").is_file() {
+                let name = path.file_name()?.to_string_lossy().to_string();
+                Some((name, path.join("main.rs")))
+            } else {
+                None
+            }
+        })
+        .collect();
+    bins.sort();
+    bins
+}
+
+/// Get the sorted and deduplicated paths of the parsed source files.
+///
+/// Paths are relative to the project root (see project_path).
+///
+/// ### Parameters
+/// * `modules` - Root nodes of the parsed files.
+/// * `project_root` - Canonical path of the project root, if it exists.
+///
+/// ### Returns
+/// Paths of the parsed files with `/` separators.
+fn source_files(modules: &[RustTraceableNode], project_root: Option<&Path>) -> Vec<String> {
+    let mut files: Vec<String> = modules
+        .iter()
+        .map(|module| project_path(&module.location.filename, project_root))
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// Schema name and version of implementation trace documents.
+const IMP_SCHEMA: (&str, u32) = ("lobster-imp-trace", 3);
+
+/// Schema name and version of activity trace documents.
+const ACT_SCHEMA: (&str, u32) = ("lobster-act-trace", 3);
+
+/// Schema name and version of requirement trace documents.
+const REQ_SCHEMA: (&str, u32) = ("lobster-req-trace", 4);
+
+/// Optional metadata of an output document.
+#[derive(Clone, Copy)]
+struct DocumentMetadata<'a> {
+    /// JSON schema URL to reference via "$schema".
+    schema_ref: Option<&'a str>,
+    /// Name and version of the parsed crate.
+    crate_info: Option<&'a CrateInfo>,
+    /// Parsed source files to list in "files".
+    files: Option<&'a [String]>,
+}
+
+/// Build a document in the lobster common interchange format.
+///
+/// Combines parsed data and fixed information to full lobster common interchange format output.
+///
+/// ### Parameters
+/// * `data` - Items to put in the data field of the document.
+/// * `schema` - Schema name and version of the document.
+/// * `metadata` - Optional metadata to add to the document.
+///
+/// ### Returns
+/// JsonValue holding the full document.
+fn lobster_document(
+    data: Vec<JsonValue>,
+    schema: (&str, u32),
+    metadata: &DocumentMetadata,
+) -> JsonValue {
     let mut jout = JsonValue::Object(Object::new());
+    if let Some(schema_ref) = metadata.schema_ref {
+        let _ = jout.insert("$schema", schema_ref);
+    }
+    if let Some(crate_info) = metadata.crate_info {
+        let _ = jout.insert("crate_name", crate_info.name.as_str());
+        if let Some(version) = &crate_info.version {
+            let _ = jout.insert("crate_version", version.as_str());
+        }
+    }
     let _ = jout.insert("data", data);
+    if let Some(files) = metadata.files {
+        let _ = jout.insert("files", files);
+    }
     let _ = jout.insert("generator", "lobster-rust");
-    let _ = jout.insert("schema", "lobster-imp-trace");
-    let _ = jout.insert("version", 3);
+    let _ = jout.insert("schema", schema.0);
+    let _ = jout.insert("version", schema.1);
+    jout
+}
 
-    // Write lobster common interchange format to output file.
-    let outfile: &Path = Path::new(&args.out);
-    match File::create(outfile) {
-        // Panic if we cant write the results. Print error details.
-        Err(e) => panic!("Outfile: {:#?}\n{}", &outfile, e),
-        Ok(outfile) => {
-            let mut outwriter = BufWriter::new(outfile);
-            let _ = jout.write_pretty(&mut outwriter, 4);
+/// Write a lobster common interchange format document to a file.
+///
+/// The document is written to stdout instead if the path is `-`. Panics if the document can not be
+/// written completely, so that a truncated document leads to a non-zero exit code.
+///
+/// ### Parameters
+/// * `outfile` - Path of the file to write, or `-` for stdout.
+/// * `jout` - Document to write.
+/// * `format` - Serialization format of the document.
+fn write_lobster(outfile: &Path, jout: &JsonValue, format: args::OutputFormat) {
+    let written = if Path::new("-") == outfile {
+        write_document(
+            &mut BufWriter::new(io::stdout()),
+            jout,
+            format.to_document_format(),
+        )
+    } else {
+        match File::create(outfile) {
+            // Panic if we cant write the results. Print error details.
+            Err(e) => panic!("Outfile: {:#?}\n{}", &outfile, e),
+            Ok(file) => {
+                write_document(&mut BufWriter::new(file), jout, format.to_document_format())
+            }
         }
+    };
+    if let Err(e) = written {
+        panic!("Outfile: {:#?}\n{}", &outfile, e);
     }
 }
 
 /// Submodule to define the tools CLI.
 #[allow(unused_parens)]
 mod args {
-    use clap::Parser;
+    use clap::{Parser, ValueEnum};
+    use lobster_rust::output::DocumentFormat;
+    use lobster_rust::traceable_node::{NodeKind, OutputOptions};
+    use lobster_rust::visitor::LocateAt;
+    use std::collections::HashMap;
+    use std::num::NonZeroUsize;
+
+    /// Kinds of items that can be required to be traced.
+    #[derive(Debug, Clone, Copy, ValueEnum)]
+    pub(super) enum TracedKind {
+        /// Functions and methods.
+        Function,
+        /// Structs.
+        Struct,
+        /// Macro invocations at item position.
+        Macro,
+    }
+
+    impl TracedKind {
+        /// Get the NodeKind corresponding to the TracedKind.
+        pub(super) fn to_node_kind(self) -> NodeKind {
+            match self {
+                TracedKind::Function => NodeKind::Function,
+                TracedKind::Struct => NodeKind::Struct,
+                TracedKind::Macro => NodeKind::MacroInvocation,
+            }
+        }
+    }
+
+    /// Parts of items that locations can point to.
+    #[derive(Debug, Clone, Copy, ValueEnum)]
+    pub(super) enum LocationTarget {
+        /// The keyword of the item, e.g. `fn`.
+        Keyword,
+        /// The name of the item.
+        Name,
+    }
+
+    impl LocationTarget {
+        /// Get the LocateAt corresponding to the LocationTarget.
+        pub(super) fn to_locate_at(self) -> LocateAt {
+            match self {
+                LocationTarget::Keyword => LocateAt::Keyword,
+                LocationTarget::Name => LocateAt::Name,
+            }
+        }
+    }
+
+    /// Serialization formats of the output documents.
+    #[derive(Debug, Clone, Copy, ValueEnum)]
+    pub(super) enum OutputFormat {
+        /// JSON, the lobster common interchange format.
+        Json,
+        /// YAML with the same structure as the JSON output.
+        Yaml,
+    }
+
+    impl OutputFormat {
+        /// Get the DocumentFormat corresponding to the OutputFormat.
+        pub(super) fn to_document_format(self) -> DocumentFormat {
+            match self {
+                OutputFormat::Json => DocumentFormat::Json,
+                OutputFormat::Yaml => DocumentFormat::Yaml,
+            }
+        }
+    }
+
+    /// Kinds of items that can be emitted as requirements.
+    #[derive(Debug, Clone, Copy, ValueEnum)]
+    pub(super) enum RequirementKind {
+        /// Trait definitions.
+        Trait,
+        /// Structs.
+        Struct,
+    }
+
+    impl RequirementKind {
+        /// Get the NodeKind corresponding to the RequirementKind.
+        pub(super) fn to_node_kind(self) -> NodeKind {
+            match self {
+                RequirementKind::Trait => NodeKind::Trait,
+                RequirementKind::Struct => NodeKind::Struct,
+            }
+        }
+    }
+
+    /// Kinds of items that can be skipped in the output.
+    #[derive(Debug, Clone, Copy, ValueEnum)]
+    pub(super) enum SkippedKind {
+        /// Functions and methods.
+        Function,
+        /// Structs.
+        Struct,
+        /// Enums.
+        Enum,
+        /// Annotated fields of structs.
+        Field,
+        /// Annotated variants of enums.
+        Variant,
+        /// Macro invocations at item position.
+        Macro,
+        /// Macro definitions (`macro name() {}`).
+        MacroDef,
+        /// Annotated modules.
+        Module,
+        /// Names re-exported via `pub use`.
+        ReExport,
+    }
+
+    impl SkippedKind {
+        /// Get the NodeKind corresponding to the SkippedKind.
+        pub(super) fn to_node_kind(self) -> NodeKind {
+            match self {
+                SkippedKind::Function => NodeKind::Function,
+                SkippedKind::Struct => NodeKind::Struct,
+                SkippedKind::Enum => NodeKind::Enum,
+                SkippedKind::Field => NodeKind::Field,
+                SkippedKind::Variant => NodeKind::Variant,
+                SkippedKind::Macro => NodeKind::MacroInvocation,
+                SkippedKind::MacroDef => NodeKind::Macro,
+                SkippedKind::Module => NodeKind::Module,
+                SkippedKind::ReExport => NodeKind::ReExport,
+            }
+        }
+    }
+
     #[derive(Parser)]
     #[command(version, about, long_about = None)]
     pub(super) struct Cli {
-        /// Directory of main.rs (or lib.rs).
+        /// Directory of main.rs (or lib.rs), or the entry file itself.
         #[arg(default_value_t = ("./src/".to_string()))]
         pub(super) dir: String,
 
-        /// Output file for the lobster common interchange format output.
+        /// Output file for the lobster common interchange format output, - for stdout.
         #[arg(default_value_t = ("rust.lobster".to_string()))]
         pub(super) out: String,
 
+        /// Serialization format of the output documents.
+        #[arg(long, value_enum, default_value = "json")]
+        pub(super) format: OutputFormat,
+
         /// Parse lib.rs as project root instead of main.rs.
         #[arg(short, long)]
         pub(super) lib: bool,
 
-        /// Generate activity traces (tests) instead of an implementation trace. UNSUPPORTED.
+        /// Generate activity traces (tests) instead of an implementation trace.
         #[arg(long)]
         pub(super) activity: bool,
 
+        /// Additional attribute paths marking test functions in activity traces, besides test,
+        /// tokio::test and async_std::test.
+        #[arg(long, value_delimiter = ',')]
+        pub(super) test_attrs: Vec<String>,
+
         /// Only trace functions with tags. UNSUPPORTED.
         #[arg(long)]
         pub(super) only_tagged_functions: bool,
+
+        /// Prefix of the tracing tags in the output.
+        #[arg(long, default_value_t = ("rust".to_string()))]
+        pub(super) tag_prefix: String,
+
+        /// Additionally parse the binary src/bin/<BIN>.rs (or src/bin/<BIN>/main.rs). Can be given
+        /// multiple times.
+        #[arg(long)]
+        pub(super) bin: Vec<String>,
+
+        /// Additionally parse all binaries in src/bin.
+        #[arg(long)]
+        pub(super) all_bins: bool,
+
+        /// Additionally parse the build script build.rs next to DIR.
+        #[arg(long)]
+        pub(super) build_script: bool,
+
+        /// Additionally parse the crate roots listed in the TOML manifest FILE, each as a
+        /// `[[root]]` table with the `path` of the root file (relative to FILE) and the `context`
+        /// its items are named after.
+        #[arg(long, value_name = "FILE")]
+        pub(super) roots: Option<String>,
+
+        /// Parse only the crate roots of the roots manifest instead of main.rs (or lib.rs) in DIR.
+        #[arg(long, requires = "roots", conflicts_with = "input_list")]
+        pub(super) roots_only: bool,
+
+        /// Parse the bodies of macro calls at item position as item lists and trace the functions
+        /// and structs defined in them. Best-effort, as macros are not expanded.
+        #[arg(long)]
+        pub(super) parse_macro_bodies: bool,
+
+        /// Emit 0-based columns instead of 1-based columns.
+        #[arg(long)]
+        pub(super) zero_based_columns: bool,
+
+        /// Part of the items their location points to.
+        #[arg(long, value_enum, default_value = "keyword")]
+        pub(super) locate_at: LocationTarget,
+
+        /// Resolve module declarations case-insensitively if no exact match is found.
+        #[arg(long)]
+        pub(super) case_insensitive_modules: bool,
+
+        /// Skip files ignored by the nearest .gitignore when resolving modules and discovering
+        /// binaries.
+        #[arg(long)]
+        pub(super) respect_gitignore: bool,
+
+        /// Name of the top-level context in tags, e.g. the crate name. Defaults to the filename.
+        #[arg(long)]
+        pub(super) root_name: Option<String>,
+
+        /// Name the items of every file after the file itself instead of its module path, e.g.
+        /// nested.item instead of main.sub.nested.item.
+        #[arg(long)]
+        pub(super) file_as_context: bool,
+
+        /// Trace enums like structs, including variants annotated via doc comments.
+        #[arg(long)]
+        pub(super) trace_enums: bool,
+
+        /// Skip module declarations and local modules gated by an outer #[cfg(test)] attribute.
+        #[arg(long)]
+        pub(super) cfg_filter: bool,
+
+        /// Emit file paths relative to the root of the enclosing git repository instead of the
+        /// filename.
+        #[arg(long)]
+        pub(super) relative_to_git_root: bool,
+
+        /// Emit files as absolute file URIs with the line as fragment, e.g. for jumping to the
+        /// code from a report.
+        #[arg(long, conflicts_with = "relative_to_git_root")]
+        pub(super) file_uri: bool,
+
+        /// Emit a "complexity" object for functions, with the number of parameters, generic
+        /// parameters and statements of the body.
+        #[arg(long)]
+        pub(super) complexity: bool,
+
+        /// Keep at most N of the innermost namespaces in tags, truncating outer namespaces.
+        #[arg(long, value_name = "N")]
+        pub(super) max_context_depth: Option<usize>,
+
+        /// Name items by their own identifier only, without namespaces. Colliding tags get the
+        /// file and line of the item appended.
+        #[arg(long, conflicts_with = "max_context_depth")]
+        pub(super) no_context: bool,
+
+        /// Remove the given prefix from the captured requirement references, e.g. CompanyProj.
+        /// turns CompanyProj.SW.REQ.1 into SW.REQ.1. Other references are kept unchanged.
+        #[arg(long, value_name = "PREFIX")]
+        pub(super) strip_prefix: Option<String>,
+
+        /// Only emit items with at least one reference starting with the given prefix.
+        #[arg(long, value_name = "PREFIX")]
+        pub(super) ref_filter: Option<String>,
+
+        /// JSON object overriding the kind strings in the output, e.g. '{"Function": "function"}'.
+        /// Kinds not in the object keep their default kind string.
+        #[arg(long, value_name = "JSON", value_parser = OutputOptions::parse_kind_map)]
+        pub(super) kind_map: Option<HashMap<String, String>>,
+
+        /// Reference the given JSON schema URL via "$schema" in the output.
+        #[arg(long)]
+        pub(super) schema_ref: Option<String>,
+
+        /// Add a top-level "files" array to the output, listing the parsed source files relative
+        /// to the parent of DIR.
+        #[arg(long)]
+        pub(super) emit_files: bool,
+
+        /// Exit with code 1 if any item of the required kinds has neither references nor
+        /// justifications.
+        #[arg(long)]
+        pub(super) fail_on_untraced: bool,
+
+        /// Kinds of items that are required to be traced by --fail-on-untraced and --junit.
+        #[arg(long, value_enum, value_delimiter = ',', default_value = "function")]
+        pub(super) require_trace_on: Vec<TracedKind>,
+
+        /// Write a JUnit XML report to the given file, with a failing test case per untraced item
+        /// of the required kinds and a passing one per traced item.
+        #[arg(long, value_name = "FILE")]
+        pub(super) junit: Option<String>,
+
+        /// Attach requirement references from a sidecar file (tab separated path, line and ref).
+        #[arg(long)]
+        pub(super) sidecar: Option<String>,
+
+        /// Cargo.toml manifest to read the crate name and version from. Defaults to the Cargo.toml
+        /// next to DIR, if there is one.
+        #[arg(long)]
+        pub(super) manifest: Option<String>,
+
+        /// Validate references against a list of valid requirements (one per line, or a lobster
+        /// file). Unknown references are reported as warnings.
+        #[arg(long)]
+        pub(super) requirements: Option<String>,
+
+        /// Report unknown references as errors and exit with code 1, requires --requirements.
+        #[arg(long, requires = "requirements")]
+        pub(super) strict_refs: bool,
+
+        /// Kinds of items to emit as requirements into a separate lobster-req-trace document
+        /// instead of as implementation items.
+        #[arg(long, value_enum, value_delimiter = ',')]
+        pub(super) as_requirements: Vec<RequirementKind>,
+
+        /// Kinds of items to leave out of the output. Items nested in skipped items, e.g. in the
+        /// body of a skipped function, are still emitted.
+        #[arg(long, value_enum, value_delimiter = ',')]
+        pub(super) skip_kinds: Vec<SkippedKind>,
+
+        /// Output file for the items emitted via --as-requirements.
+        #[arg(long, default_value = "rust_requirements.lobster")]
+        pub(super) requirements_out: String,
+
+        /// Write one output file per source file into the given directory instead of OUT.
+        #[arg(long)]
+        pub(super) split_output: Option<String>,
+
+        /// Merge the items into the JSON document FILE instead of writing OUT, de-duplicated by tag.
+        /// FILE is created if it does not exist. OUT can not be given together with --append.
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["out", "split_output", "format"])]
+        pub(super) append: Option<String>,
+
+        /// Maximum number of threads to parse files in parallel. Defaults to the available
+        /// parallelism, 1 parses all files serially.
+        #[arg(long)]
+        pub(super) threads: Option<NonZeroUsize>,
+
+        /// Parse exactly the files listed in the given file (one path per line) instead of
+        /// following module declarations. Names are derived from the paths relative to DIR.
+        #[arg(long, conflicts_with_all = ["bin", "all_bins", "build_script"])]
+        pub(super) input_list: Option<String>,
+
+        /// Report the items added, removed or with changed references compared to the given
+        /// previous lobster file.
+        #[arg(long, value_name = "OLD")]
+        pub(super) diff: Option<String>,
+
+        /// Write the report of --diff to the given file instead of stderr.
+        #[arg(long, requires = "diff")]
+        pub(super) diff_out: Option<String>,
+
+        /// Print the paths of all files that would be parsed and exit without writing output.
+        #[arg(long)]
+        pub(super) list_files: bool,
+
+        /// Print the number of items referencing each requirement, sorted descending, and exit
+        /// without writing output. With --requirements, unreferenced requirements are listed
+        /// with 0.
+        #[arg(long)]
+        pub(super) ref_histogram: bool,
+
+        /// Regenerate the output whenever a .rs file in DIR changes, until interrupted.
+        #[arg(long)]
+        pub(super) watch: bool,
+
+        /// Print the JSON schema of the output and exit.
+        #[arg(long)]
+        pub(super) print_schema: bool,
+
+        /// Print the versions of lobster-rust and its parser, the parsed edition and the emitted
+        /// schema (the activity schema with --activity) and exit.
+        #[arg(long)]
+        pub(super) version_info: bool,
     }
 }
@@ -0,0 +1,108 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Crate information read from the Cargo.toml manifest of a crate.
+//!
+//! Only the `name` and `version` keys of the `[package]` table are read. Values that are not plain
+//! strings, e.g. `version.workspace = true`, are ignored.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Name and version of a crate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrateInfo {
+    /// Name of the crate.
+    pub name: String,
+    /// Version of the crate, if given as plain string.
+    pub version: Option<String>,
+}
+
+impl CrateInfo {
+    /// Load the crate information from a Cargo.toml manifest.
+    ///
+    /// ### Parameters
+    /// * `manifest_path` - Path of the Cargo.toml file.
+    ///
+    /// ### Returns
+    /// Some CrateInfo if the manifest holds a package name, None otherwise, or the error if the
+    /// file could not be read.
+    pub fn from_file(manifest_path: &Path) -> io::Result<Option<Self>> {
+        let content = fs::read_to_string(manifest_path)?;
+        Ok(CrateInfo::parse(&content))
+    }
+
+    /// Parse the content of a Cargo.toml manifest.
+    ///
+    /// ### Parameters
+    /// * `content` - Content of the manifest.
+    ///
+    /// ### Returns
+    /// Some CrateInfo if the manifest holds a package name, None otherwise.
+    pub fn parse(content: &str) -> Option<Self> {
+        let mut in_package = false;
+        let mut name = None;
+        let mut version = None;
+        for line in content.lines().map(str::trim) {
+            if line.starts_with('[') {
+                in_package = "[package]" == line;
+                continue;
+            }
+            if !in_package {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                let value = string_value(value);
+                match key.trim() {
+                    "name" => name = value,
+                    "version" => version = value,
+                    _ => (),
+                }
+            }
+        }
+        Some(CrateInfo {
+            name: name?,
+            version,
+        })
+    }
+}
+
+/// Get the content of a basic TOML string value.
+///
+/// ### Parameters
+/// * `value` - Value part of a TOML key value pair, e.g. ` "lobster-rust" # comment`.
+///
+/// ### Returns
+/// Some String with the content of the string, None if the value is no basic string.
+pub(crate) fn string_value(value: &str) -> Option<String> {
+    let value = value.trim().strip_prefix('"')?;
+    let end = value.find('"')?;
+    Some(value[..end].to_string())
+}
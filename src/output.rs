@@ -0,0 +1,174 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Serialization of the lobster common interchange format documents.
+
+use json::JsonValue;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::yaml;
+
+/// Serialization formats of the output documents.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DocumentFormat {
+    /// JSON, the lobster common interchange format.
+    #[default]
+    Json,
+    /// YAML with the same structure as the JSON output.
+    Yaml,
+}
+
+/// Serialize a document in the given format and flush the writer.
+///
+/// The writer is flushed explicitly, so that errors of buffered writes surface as well instead of
+/// silently truncating the document, e.g. when a BufWriter is dropped.
+///
+/// ### Parameters
+/// * `outwriter` - Writer to write the document to.
+/// * `jout` - Document to write.
+/// * `format` - Serialization format of the document.
+///
+/// ### Returns
+/// The error if writing or flushing failed.
+pub fn write_document(
+    outwriter: &mut impl Write,
+    jout: &JsonValue,
+    format: DocumentFormat,
+) -> io::Result<()> {
+    match format {
+        DocumentFormat::Json => jout.write_pretty(outwriter, 4)?,
+        DocumentFormat::Yaml => outwriter.write_all(yaml::to_yaml(jout).as_bytes())?,
+    }
+    outwriter.flush()
+}
+
+/// Load a lobster common interchange format document, e.g. to append to it.
+///
+/// ### Parameters
+/// * `lobster_path` - Path of the JSON document.
+///
+/// ### Returns
+/// The document, or the error if the file could not be read or is not valid json.
+pub fn load_document(lobster_path: &Path) -> io::Result<JsonValue> {
+    let content = fs::read_to_string(lobster_path)?;
+    json::parse(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Merge a new document into an existing document.
+///
+/// The data items are de-duplicated by tag. Items of the new document replace the items of the
+/// existing document with the same tag, keeping their position. Every replacement of an item at a
+/// different location, e.g. a different item that happens to have the same tag, and of an item
+/// with different references is reported as conflict. Other new items are appended. The listed
+/// files of both documents are combined, all other fields are taken from the new document.
+///
+/// ### Parameters
+/// * `existing` - Document that is appended to.
+/// * `new` - Document holding the items to append.
+///
+/// ### Returns
+/// Tuple of the merged document and the descriptions of the conflicts, or an error if the
+/// documents are of different schemas.
+pub fn merge_documents(
+    existing: &JsonValue,
+    mut new: JsonValue,
+) -> io::Result<(JsonValue, Vec<String>)> {
+    if existing["schema"] != new["schema"] {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Can not append {} items to a {} document",
+                new["schema"], existing["schema"]
+            ),
+        ));
+    }
+
+    let mut conflicts = Vec::new();
+    let mut data: Vec<JsonValue> = existing["data"].members().cloned().collect();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for (position, item) in data.iter().enumerate() {
+        if let Some(tag) = item["tag"].as_str() {
+            let _ = positions.insert(tag.to_string(), position);
+        }
+    }
+    for item in new["data"].members() {
+        match item["tag"].as_str().and_then(|tag| positions.get(tag)) {
+            Some(&position) => {
+                if data[position]["location"] != item["location"] {
+                    conflicts.push(format!(
+                        "Replacing {} at {} by the item at {} while appending.",
+                        item["tag"],
+                        location_text(&data[position]),
+                        location_text(item)
+                    ));
+                } else if data[position]["refs"] != item["refs"] {
+                    conflicts.push(format!(
+                        "Conflicting references of {} while appending, keeping {}.",
+                        item["tag"], item["refs"]
+                    ));
+                }
+                data[position] = item.clone();
+            }
+            None => {
+                if let Some(tag) = item["tag"].as_str() {
+                    let _ = positions.insert(tag.to_string(), data.len());
+                }
+                data.push(item.clone());
+            }
+        }
+    }
+    new["data"] = JsonValue::Array(data);
+
+    if existing["files"].is_array() && new["files"].is_array() {
+        let mut files: Vec<JsonValue> = existing["files"].members().cloned().collect();
+        files.extend(new["files"].members().cloned());
+        files.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+        files.dedup();
+        new["files"] = JsonValue::Array(files);
+    }
+    Ok((new, conflicts))
+}
+
+/// Get the location of an item as text, e.g. `main:3`.
+///
+/// ### Parameters
+/// * `item` - Item in the lobster common interchange format.
+///
+/// ### Returns
+/// The file and line of the item, or only the file if the item has no line.
+fn location_text(item: &JsonValue) -> String {
+    let location = &item["location"];
+    match location["line"].as_usize() {
+        Some(line) => format!("{}:{}", location["file"], line),
+        None => location["file"].to_string(),
+    }
+}
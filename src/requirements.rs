@@ -0,0 +1,170 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Lists of valid requirements to validate the references of items.
+//!
+//! A requirements file is either a plain list holding one requirement per line, e.g.
+//! `something.example`, or a lobster common interchange format file, whose item tags are used
+//! (e.g. `req something.example`). Empty lines and lines starting with `#` of plain lists are
+//! ignored.
+
+use json::JsonValue;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::traceable_node::RustTraceableNode;
+
+/// Set of valid requirements, without the `req` namespace of the references.
+#[derive(Debug, Default)]
+pub struct Requirements {
+    ids: HashSet<String>,
+}
+
+impl Requirements {
+    /// Load a requirements file.
+    ///
+    /// ### Parameters
+    /// * `requirements_path` - Path of the requirements file.
+    ///
+    /// ### Returns
+    /// The loaded Requirements, or the error if the file could not be read or parsed.
+    pub fn from_file(requirements_path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(requirements_path)?;
+        Requirements::parse(&content)
+    }
+
+    /// Parse the content of a requirements file.
+    ///
+    /// Content starting with `{` is parsed as lobster common interchange format, any other content
+    /// as plain list.
+    ///
+    /// ### Parameters
+    /// * `content` - Content of the requirements file.
+    ///
+    /// ### Returns
+    /// The parsed Requirements, or the error if a lobster file is not valid json.
+    pub fn parse(content: &str) -> io::Result<Self> {
+        let ids = if content.trim_start().starts_with('{') {
+            let lobster =
+                json::parse(content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            lobster["data"]
+                .members()
+                .filter_map(|item| item["tag"].as_str())
+                .map(|tag| requirement_id(tag).to_string())
+                .collect()
+        } else {
+            content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(|line| line.to_string())
+                .collect()
+        };
+        Ok(Requirements { ids })
+    }
+
+    /// Find references to requirements that are not in the set of valid requirements.
+    ///
+    /// Searches the node and recursively all of its children.
+    ///
+    /// ### Parameters
+    /// * `node` - Node to check.
+    ///
+    /// ### Returns
+    /// The nodes with unknown references together with the reference, in the order of the tree.
+    pub fn find_unknown<'a>(
+        &self,
+        node: &'a RustTraceableNode,
+    ) -> Vec<(&'a RustTraceableNode, String)> {
+        let mut unknown: Vec<(&RustTraceableNode, String)> = node
+            .refs
+            .iter()
+            .filter(|reference| !self.ids.contains(requirement_id(reference)))
+            .map(|reference| (node, reference.clone()))
+            .collect();
+        for child in node.children.iter() {
+            unknown.append(&mut self.find_unknown(child));
+        }
+        unknown
+    }
+}
+
+/// Count the items referencing each requirement.
+///
+/// Every item counts once per referenced requirement, even if it references the requirement
+/// several times. With a list of valid requirements, requirements without any referencing item
+/// are included with a count of 0.
+///
+/// ### Parameters
+/// * `items` - Items in the lobster common interchange format, as written to the output.
+/// * `requirements` - Optional list of valid requirements.
+///
+/// ### Returns
+/// The requirement ids with the number of referencing items, sorted by descending count and id.
+pub fn ref_histogram(
+    items: &[JsonValue],
+    requirements: Option<&Requirements>,
+) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    if let Some(requirements) = requirements {
+        for id in requirements.ids.iter() {
+            let _ = counts.insert(id.clone(), 0);
+        }
+    }
+    for item in items.iter() {
+        let mut ids: Vec<&str> = item["refs"]
+            .members()
+            .filter_map(JsonValue::as_str)
+            .map(requirement_id)
+            .collect();
+        ids.sort();
+        ids.dedup();
+        for id in ids {
+            *counts.entry(id.to_string()).or_insert(0) += 1;
+        }
+    }
+    let mut histogram: Vec<(String, usize)> = counts.into_iter().collect();
+    histogram.sort_by(|(id_a, count_a), (id_b, count_b)| {
+        count_b.cmp(count_a).then_with(|| id_a.cmp(id_b))
+    });
+    histogram
+}
+
+/// Get the requirement id of a tag or reference, i.e. the tag without namespace.
+///
+/// ### Parameters
+/// * `tag` - Tag or reference, e.g. `req something.example`.
+///
+/// ### Returns
+/// The requirement id, e.g. `something.example`.
+fn requirement_id(tag: &str) -> &str {
+    tag.split_once(' ').map_or(tag, |(_, id)| id)
+}
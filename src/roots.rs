@@ -0,0 +1,122 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Manifest of additional crate roots, e.g. independent entry points not described by Cargo.
+//!
+//! The manifest is a small TOML file holding one `[[root]]` table per crate root, with the path of
+//! the root file and the context its items are named after:
+//!
+//! ```toml
+//! [[root]]
+//! path = "tools/gen/main.rs"
+//! context = "gen"
+//! ```
+//!
+//! Paths are relative to the directory of the manifest. Only basic string values are supported,
+//! other tables and keys are ignored.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::manifest::string_value;
+
+/// Crate root listed in a roots manifest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Root {
+    /// Path of the root file, e.g. main.rs.
+    pub path: PathBuf,
+    /// Name of the top-level context of the items of the root, replacing the filename.
+    pub context: String,
+}
+
+/// Load the crate roots of a roots manifest.
+///
+/// Incomplete roots are skipped with a warning.
+///
+/// ### Parameters
+/// * `manifest_path` - Path of the roots manifest.
+///
+/// ### Returns
+/// The roots in the order of the manifest and the warnings about incomplete roots, or the error if
+/// the file could not be read.
+pub fn load_roots(manifest_path: &Path) -> io::Result<(Vec<Root>, Vec<String>)> {
+    let content = fs::read_to_string(manifest_path)?;
+    let base_dir = manifest_path.parent().unwrap_or(Path::new(""));
+    Ok(parse_roots(&content, base_dir))
+}
+
+/// Parse the content of a roots manifest.
+///
+/// ### Parameters
+/// * `content` - Content of the roots manifest.
+/// * `base_dir` - Directory the paths in the manifest are relative to.
+///
+/// ### Returns
+/// The complete roots in the order of the manifest and a warning for every incomplete root.
+pub fn parse_roots(content: &str, base_dir: &Path) -> (Vec<Root>, Vec<String>) {
+    // Path and context of every `[[root]]` table, None outside of root tables.
+    let mut tables: Vec<(Option<String>, Option<String>)> = Vec::new();
+    let mut in_root = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_root = "[[root]]" == line;
+            if in_root {
+                tables.push((None, None));
+            }
+            continue;
+        }
+        let Some(table) = tables.last_mut().filter(|_| in_root) else {
+            continue;
+        };
+        if let Some((key, value)) = line.split_once('=') {
+            match key.trim() {
+                "path" => table.0 = string_value(value),
+                "context" => table.1 = string_value(value),
+                _ => (),
+            }
+        }
+    }
+
+    let mut roots = Vec::new();
+    let mut warnings = Vec::new();
+    for (index, table) in tables.into_iter().enumerate() {
+        match table {
+            (Some(path), Some(context)) if !context.is_empty() => roots.push(Root {
+                path: base_dir.join(path),
+                context,
+            }),
+            _ => warnings.push(format!(
+                "Root {} needs a path and a context. Continuing...",
+                index + 1
+            )),
+        }
+    }
+    (roots, warnings)
+}
@@ -0,0 +1,115 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! JSON schema of the lobster common interchange format produced by lobster-rust.
+
+use json::{object, JsonValue};
+
+/// JSON schema dialect the generated schema is written in.
+const SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Generate the JSON schema of the lobster-rust output.
+///
+/// The schema describes the document produced by lobster-rust, including the shape of the data
+/// items converted from RustTraceableNodes. It allows downstream tooling to validate the output.
+///
+/// ### Returns
+/// JsonValue holding the JSON schema.
+pub fn lobster_schema() -> JsonValue {
+    object! {
+        "$schema": SCHEMA_DIALECT,
+        "title": "lobster-rust implementation trace",
+        "type": "object",
+        "required": ["data", "generator", "schema", "version"],
+        "properties": {
+            "$schema": { "type": "string" },
+            "data": { "type": "array", "items": item_schema() },
+            "files": { "type": "array", "items": { "type": "string" } },
+            "generator": { "const": "lobster-rust" },
+            "schema": { "const": "lobster-imp-trace" },
+            "version": { "const": 3 }
+        }
+    }
+}
+
+/// Generate the JSON schema of a single data item.
+///
+/// ### Returns
+/// JsonValue holding the JSON schema of a data item.
+fn item_schema() -> JsonValue {
+    let string_array = object! { "type": "array", "items": { "type": "string" } };
+    let optional_position = object! { "type": ["integer", "null"], "minimum": 0 };
+
+    object! {
+        "type": "object",
+        "required": [
+            "tag", "name", "location", "messages", "just_up", "just_down", "just_global", "refs",
+            "scope", "parent_tag", "language", "kind"
+        ],
+        "properties": {
+            "tag": { "type": "string" },
+            "name": { "type": "string" },
+            "location": {
+                "type": "object",
+                "required": ["kind", "file", "line", "column"],
+                "properties": {
+                    "kind": { "const": "file" },
+                    "file": { "type": "string" },
+                    "line": optional_position.clone(),
+                    "column": optional_position
+                }
+            },
+            "messages": string_array.clone(),
+            "just_up": string_array.clone(),
+            "just_down": string_array.clone(),
+            "just_global": string_array.clone(),
+            "refs": string_array.clone(),
+            "scope": string_array.clone(),
+            "parent_tag": { "type": ["string", "null"] },
+            "language": { "const": "Rust" },
+            "kind": { "type": "string" },
+            "async": { "type": "boolean" },
+            "const": { "type": "boolean" },
+            "unsafe": { "type": "boolean" },
+            "symbol": { "type": "string" },
+            "complexity": {
+                "type": "object",
+                "required": ["parameters", "generic_params"],
+                "properties": {
+                    "parameters": { "type": "integer", "minimum": 0 },
+                    "generic_params": { "type": "integer", "minimum": 0 },
+                    "statements": { "type": "integer", "minimum": 0 }
+                }
+            },
+            "message": { "type": "string" },
+            "non_exhaustive": { "const": true },
+            "cfg": string_array
+        }
+    }
+}
@@ -0,0 +1,157 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Sidecar files to attach requirement references to items without modifying the source code.
+//!
+//! A sidecar file holds one reference per line, consisting of the path of the source file, the
+//! line of the item and the requirement reference, separated by tabs. For example the line
+//! `src/main.rs<TAB>12<TAB>something.example` traces the item in line 12 of src/main.rs to
+//! `req something.example`.
+//!
+//! Paths are relative to the directory of the sidecar file. Empty lines and lines starting with
+//! `#` are ignored.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::traceable_node::{NodeKind, RustTraceableNode};
+
+/// Requirement references loaded from a sidecar file, by source file and line.
+///
+/// The references are attached to the parsed items via apply, in addition to the references
+/// annotated in the source code.
+#[derive(Debug, Default)]
+pub struct Sidecar {
+    /// Requirement references by (canonical) source file path and line.
+    refs: HashMap<(PathBuf, usize), Vec<String>>,
+}
+
+impl Sidecar {
+    /// Load a sidecar file.
+    ///
+    /// Malformed lines are skipped with a warning.
+    ///
+    /// ### Parameters
+    /// * `sidecar_path` - Path of the sidecar file.
+    ///
+    /// ### Returns
+    /// The loaded Sidecar and the warnings about malformed lines, or the error if the file could
+    /// not be read.
+    pub fn from_file(sidecar_path: &Path) -> std::io::Result<(Self, Vec<String>)> {
+        let content = fs::read_to_string(sidecar_path)?;
+        let base_dir = sidecar_path.parent().unwrap_or(Path::new(""));
+        Ok(Sidecar::parse(&content, base_dir))
+    }
+
+    /// Parse the content of a sidecar file.
+    ///
+    /// ### Parameters
+    /// * `content` - Content of the sidecar file.
+    /// * `base_dir` - Directory the paths in the sidecar file are relative to.
+    ///
+    /// ### Returns
+    /// The parsed Sidecar and a warning for every malformed line.
+    pub fn parse(content: &str, base_dir: &Path) -> (Self, Vec<String>) {
+        let mut sidecar = Sidecar::default();
+        let mut warnings = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+            match fields[..] {
+                [path, line_number, reference] if !reference.is_empty() => {
+                    if let Ok(line_number) = line_number.parse::<usize>() {
+                        let key = (normalize_path(&base_dir.join(path)), line_number);
+                        sidecar
+                            .refs
+                            .entry(key)
+                            .or_default()
+                            .push(format!("req {}", reference));
+                        continue;
+                    }
+                }
+                _ => (),
+            }
+            warnings.push(format!(
+                "Malformed sidecar line {}: {:?}. Continuing...",
+                index + 1,
+                line
+            ));
+        }
+        (sidecar, warnings)
+    }
+
+    /// Attach the requirement references to the items of a source file.
+    ///
+    /// References are attached to the functions and structs located at the line given in the
+    /// sidecar file.
+    ///
+    /// ### Parameters
+    /// * `root_node` - Root node of a parsed source file, located at the path of the file.
+    pub fn apply(&self, root_node: &mut RustTraceableNode) {
+        let source_path = normalize_path(Path::new(&root_node.location.filename));
+        self.apply_to_children(&source_path, root_node);
+    }
+
+    /// Recursively attach the requirement references to the children of a node.
+    ///
+    /// ### Parameters
+    /// * `source_path` - Normalized path of the source file the node is located in.
+    /// * `node` - Node whose children get the references.
+    fn apply_to_children(&self, source_path: &Path, node: &mut RustTraceableNode) {
+        for child in node.children.iter_mut() {
+            if [NodeKind::Function, NodeKind::Struct].contains(&child.kind) {
+                if let Some(line) = child.location.line {
+                    if let Some(refs) = self.refs.get(&(source_path.to_path_buf(), line)) {
+                        child.refs.extend(refs.iter().cloned());
+                    }
+                }
+            }
+            self.apply_to_children(source_path, child);
+        }
+    }
+}
+
+/// Normalize a path for comparison.
+///
+/// The paths of the sidecar file and of the parsed files may refer to the same file in different
+/// ways, e.g. `src/./main.rs` and `/crate/src/main.rs`. Both are compared in their canonical form.
+///
+/// ### Parameters
+/// * `path` - Path to normalize.
+///
+/// ### Returns
+/// The canonical path if the file exists, the path as is otherwise.
+fn normalize_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
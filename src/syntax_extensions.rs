@@ -60,21 +60,38 @@ impl Visitable for SyntaxNode {
     /// The node is visited by first calling the visitors node_enter method.
     /// Then the nodes children (nodes and tokens) are visited in order.
     /// Finally, the visitors node_exit is called.
+    /// The tree is traversed with an explicit work stack instead of recursion, so deeply nested
+    /// trees (e.g. generated expressions) can not overflow the native stack.
     ///
     /// ### Parameters
     /// * `visitor` - struct implementing the Visitor trait.
     fn visit(&self, visitor: &mut dyn Visitor) {
-        visitor.node_enter(self);
-
-        // Iterate over all subnodes and tokens contained in this node.
-        for child in self.children_with_tokens() {
-            child.visit(visitor);
+        let mut work_stack = vec![VisitStep::Enter(NodeOrToken::Node(self.clone()))];
+
+        while let Some(step) = work_stack.pop() {
+            match step {
+                VisitStep::Enter(NodeOrToken::Node(node)) => {
+                    visitor.node_enter(&node);
+                    // The exit is due after all children, which are visited in order.
+                    let children: Vec<SyntaxElement> = node.children_with_tokens().collect();
+                    work_stack.push(VisitStep::Exit(node));
+                    work_stack.extend(children.into_iter().rev().map(VisitStep::Enter));
+                }
+                VisitStep::Enter(NodeOrToken::Token(token)) => token.visit(visitor),
+                VisitStep::Exit(node) => visitor.node_exit(&node),
+            }
         }
-
-        visitor.node_exit(self);
     }
 }
 
+/// Pending step of the traversal of a syntax tree.
+enum VisitStep {
+    /// Enter a node or visit a token.
+    Enter(SyntaxElement),
+    /// Exit a node after all of its children were visited.
+    Exit(SyntaxNode),
+}
+
 impl Visitable for SyntaxToken {
     /// Visits the token.
     ///
@@ -0,0 +1,166 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Comparison of lobster traces to review the tracing impact of a change.
+//!
+//! A trace is compared by the tags of its items: items whose tag only exists in the new trace are
+//! added, items whose tag only exists in the old trace are removed, and items with the same tag but
+//! different references are changed.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use json::JsonValue;
+
+/// References of the items of a trace, by tag.
+pub type TraceRefs = BTreeMap<String, Vec<String>>;
+
+/// Differences between two lobster traces.
+#[derive(Debug, Default, PartialEq)]
+pub struct TraceDiff {
+    /// Tags of items only in the new trace.
+    pub added: Vec<String>,
+    /// Tags of items only in the old trace.
+    pub removed: Vec<String>,
+    /// Tags of items in both traces with their old and new references, if the references differ.
+    pub changed: Vec<(String, Vec<String>, Vec<String>)>,
+}
+
+impl TraceDiff {
+    /// Compare two traces.
+    ///
+    /// ### Parameters
+    /// * `old` - References of the items of the old trace.
+    /// * `new` - References of the items of the new trace.
+    ///
+    /// ### Returns
+    /// The differences, sorted by tag.
+    pub fn new(old: &TraceRefs, new: &TraceRefs) -> Self {
+        let mut diff = TraceDiff::default();
+        for (tag, new_refs) in new.iter() {
+            match old.get(tag) {
+                None => diff.added.push(tag.clone()),
+                Some(old_refs) if old_refs != new_refs => {
+                    diff.changed
+                        .push((tag.clone(), old_refs.clone(), new_refs.clone()));
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed = old
+            .keys()
+            .filter(|tag| !new.contains_key(*tag))
+            .cloned()
+            .collect();
+        diff
+    }
+
+    /// Checks if the traces are equal.
+    ///
+    /// ### Returns
+    /// true if no item was added, removed or changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl fmt::Display for TraceDiff {
+    /// Format the differences as report, one line per item.
+    ///
+    /// Lines are prefixed with `+` for added, `-` for removed and `~` for changed items. Changed
+    /// items list the old and new references.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for tag in self.added.iter() {
+            writeln!(f, "+ {}", tag)?;
+        }
+        for tag in self.removed.iter() {
+            writeln!(f, "- {}", tag)?;
+        }
+        for (tag, old_refs, new_refs) in self.changed.iter() {
+            writeln!(
+                f,
+                "~ {}: [{}] -> [{}]",
+                tag,
+                old_refs.join(", "),
+                new_refs.join(", ")
+            )?;
+        }
+        write!(
+            f,
+            "{} added, {} removed, {} changed",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        )
+    }
+}
+
+/// Load the references of the items of a lobster file.
+///
+/// ### Parameters
+/// * `lobster_path` - Path of the lobster common interchange format file.
+///
+/// ### Returns
+/// The references by tag, or the error if the file could not be read or is not valid json.
+pub fn load_trace(lobster_path: &Path) -> io::Result<TraceRefs> {
+    let content = fs::read_to_string(lobster_path)?;
+    let lobster =
+        json::parse(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(trace_refs(lobster["data"].members()))
+}
+
+/// Collect the references of lobster items by tag.
+///
+/// References are sorted, so that a reordering of the references is not reported as change.
+/// Items without tag are skipped.
+///
+/// ### Parameters
+/// * `items` - Items in the lobster common interchange format.
+///
+/// ### Returns
+/// The references by tag.
+pub fn trace_refs<'a>(items: impl IntoIterator<Item = &'a JsonValue>) -> TraceRefs {
+    let mut trace = TraceRefs::new();
+    for item in items {
+        let Some(tag) = item["tag"].as_str() else {
+            continue;
+        };
+        let mut refs: Vec<String> = item["refs"]
+            .members()
+            .filter_map(|reference| reference.as_str())
+            .map(str::to_string)
+            .collect();
+        refs.sort();
+        let _ = trace.insert(tag.to_string(), refs);
+    }
+    trace
+}
@@ -32,13 +32,18 @@
 
 use json::{object::Object, JsonValue};
 use ra_ap_syntax::{SyntaxKind, SyntaxNode};
+use std::collections::HashMap;
 use std::fmt::Display;
 
-use crate::{location::FileReference, syntax_extensions::Searchable, utils::context::Context};
+use crate::{
+    location::{forward_slashes, FileReference},
+    syntax_extensions::Searchable,
+    utils::context::Context,
+};
 
 /// Enum to define the different kinds of RustTraceableNodes.
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub(crate) enum NodeKind {
+pub enum NodeKind {
     /// The node is representing a source file.
     Source,
     /// The node is representing a struct.
@@ -51,11 +56,39 @@ pub(crate) enum NodeKind {
     Function,
     /// The node is representing some context.
     Context,
+    /// The node is representing a macro invocation at item position.
+    MacroInvocation,
+    /// The node is representing an annotated field of a struct.
+    Field,
+    /// The node is representing an annotated variant of an enum.
+    Variant,
+    /// The node is representing a macro definition (`macro name() {}`).
+    Macro,
+    /// The node is representing an annotated module.
+    Module,
+    /// The node is representing a name re-exported via `pub use`.
+    ReExport,
 }
 
 impl NodeKind {
+    /// All NodeKinds.
+    pub const ALL: [NodeKind; 12] = [
+        NodeKind::Source,
+        NodeKind::Struct,
+        NodeKind::Enum,
+        NodeKind::Trait,
+        NodeKind::Function,
+        NodeKind::Context,
+        NodeKind::MacroInvocation,
+        NodeKind::Field,
+        NodeKind::Variant,
+        NodeKind::Macro,
+        NodeKind::Module,
+        NodeKind::ReExport,
+    ];
+
     /// Returns a &str representing the NodeKind.
-    pub(crate) fn to_str(self) -> &'static str {
+    pub fn to_str(self) -> &'static str {
         match self {
             NodeKind::Source => "Module",
             NodeKind::Struct => "Struct",
@@ -63,6 +96,12 @@ impl NodeKind {
             NodeKind::Trait => "Trait",
             NodeKind::Function => "Function",
             NodeKind::Context => "Context",
+            NodeKind::MacroInvocation => "Macro Invocation",
+            NodeKind::Field => "Field",
+            NodeKind::Variant => "Variant",
+            NodeKind::Macro => "Macro",
+            NodeKind::Module => "Module",
+            NodeKind::ReExport => "ReExport",
         }
     }
 }
@@ -70,23 +109,48 @@ impl NodeKind {
 /// Struct to hold information about parsed syntax nodes.
 /// This node can be converted to data in the lobster common interchange format.
 #[derive(Debug, Clone)]
-pub(crate) struct RustTraceableNode {
+pub struct RustTraceableNode {
     /// The name of the node, produced from context and parsed information.
     /// The name is also used to construct the tracing tag when converting to the lobster common
     /// interchange format.
-    pub(crate) name: String,
+    pub name: String,
     /// The kind of the node.
-    pub(crate) kind: NodeKind,
+    pub kind: NodeKind,
     /// The location of the node.
-    pub(crate) location: FileReference,
+    pub location: FileReference,
     /// Children of the node.
-    pub(crate) children: Vec<RustTraceableNode>,
-    /// Parsed justifications.
-    pub(crate) just: Vec<String>,
+    pub children: Vec<RustTraceableNode>,
+    /// Parsed justifications for the upwards direction (missing requirement).
+    pub just_up: Vec<String>,
+    /// Parsed justifications for the downwards direction (missing tests).
+    pub just_down: Vec<String>,
+    /// Parsed global justifications.
+    pub just_global: Vec<String>,
     /// Parsed references to requirements.
-    pub(crate) refs: Vec<String>,
+    pub refs: Vec<String>,
+    /// The node is exempt from the coverage gate via a `lobster-nocheck` comment.
+    pub nocheck: bool,
     /// Optional context data to track local modules or impl blocks and resolve full names.
-    pub(crate) context_data: Option<ContextData>,
+    pub context_data: Option<ContextData>,
+    /// Exported symbol name for functions with `#[no_mangle]` or `#[export_name]` attributes.
+    pub symbol: Option<String>,
+    /// Qualifiers of the function signature, None for nodes that are not functions.
+    pub modifiers: Option<FnModifiers>,
+    /// Size of the function signature and body, None for nodes that are not functions.
+    pub complexity: Option<FnComplexity>,
+    /// Context the node is nested in, i.e. the enclosing files, modules and impl blocks.
+    pub scope: Context,
+    /// Conditions of the `#[cfg(...)]` attributes of the node, e.g. `feature = "x"`.
+    pub cfg: Vec<String>,
+    /// The node is a test function, marked by a test attribute like `#[test]`.
+    pub is_test: bool,
+    /// Names of the `#[test_case(...)]` cases of a parameterized test function (see
+    /// attributes::test_cases).
+    pub test_cases: Vec<String>,
+    /// Message of the `#[error("...")]` attribute of an enum variant, e.g. of a thiserror enum.
+    pub message: Option<String>,
+    /// The struct or enum is marked with `#[non_exhaustive]`.
+    pub non_exhaustive: bool,
 }
 
 impl RustTraceableNode {
@@ -107,12 +171,107 @@ impl RustTraceableNode {
             kind,
             location,
             children: Vec::new(),
-            just: Vec::new(),
+            just_up: Vec::new(),
+            just_down: Vec::new(),
+            just_global: Vec::new(),
             refs: Vec::new(),
+            nocheck: false,
             context_data: None,
+            symbol: None,
+            modifiers: None,
+            complexity: None,
+            scope: Context::Empty,
+            cfg: Vec::new(),
+            is_test: false,
+            test_cases: Vec::new(),
+            message: None,
+            non_exhaustive: false,
         }
     }
 
+    /// Constructs a new field RTN.
+    ///
+    /// The field is named after the struct, e.g. `main.Struct.field`, and nested in its scope.
+    ///
+    /// ### Parameters
+    /// * `struct_node` - RustTraceableNode of the struct the field belongs to.
+    /// * `field_name` - Name of the field.
+    /// * `location` - Location of the field name.
+    ///
+    /// ### Returns
+    /// A RustTraceableNode of kind Field.
+    pub(crate) fn new_field(
+        struct_node: &RustTraceableNode,
+        field_name: &str,
+        location: FileReference,
+    ) -> RustTraceableNode {
+        RustTraceableNode::new_member(struct_node, field_name, location, NodeKind::Field)
+    }
+
+    /// Constructs a new variant RTN.
+    ///
+    /// The variant is named after the enum, e.g. `main.Enum.Variant`, and nested in its scope.
+    ///
+    /// ### Parameters
+    /// * `enum_node` - RustTraceableNode of the enum the variant belongs to.
+    /// * `variant_name` - Name of the variant.
+    /// * `location` - Location of the variant name.
+    ///
+    /// ### Returns
+    /// A RustTraceableNode of kind Variant.
+    pub(crate) fn new_variant(
+        enum_node: &RustTraceableNode,
+        variant_name: &str,
+        location: FileReference,
+    ) -> RustTraceableNode {
+        RustTraceableNode::new_member(enum_node, variant_name, location, NodeKind::Variant)
+    }
+
+    /// Constructs a new RTN of a member of a struct or enum.
+    ///
+    /// ### Parameters
+    /// * `owner_node` - RustTraceableNode of the struct or enum the member belongs to.
+    /// * `member_name` - Name of the member.
+    /// * `location` - Location of the member name.
+    /// * `kind` - NodeKind of the member.
+    ///
+    /// ### Returns
+    /// A RustTraceableNode of the given kind.
+    fn new_member(
+        owner_node: &RustTraceableNode,
+        member_name: &str,
+        location: FileReference,
+        kind: NodeKind,
+    ) -> RustTraceableNode {
+        let owner_name = owner_node
+            .name
+            .rsplit('.')
+            .next()
+            .unwrap_or(&owner_node.name);
+        let mut member_node = RustTraceableNode::new(
+            format!("{}.{}", owner_node.name, member_name),
+            location,
+            kind,
+        );
+        member_node.scope = &owner_node.scope + owner_name.to_string();
+        member_node
+    }
+
+    /// Constructs a new context RTN without context data.
+    ///
+    /// Used in place of context nodes that could not be parsed, e.g. a module without a name.
+    /// Enclosed items are named as if the context did not exist.
+    ///
+    /// ### Returns
+    /// A RustTraceableNode of kind Context.
+    pub(crate) fn new_empty_context() -> RustTraceableNode {
+        RustTraceableNode::new(
+            "Context".to_string(),
+            FileReference::new_default(),
+            NodeKind::Context,
+        )
+    }
+
     /// Constructs a new RTN from a SyntaxNode.
     ///
     /// Constructs a new RustTraceableNode from a given ra_ap_syntax SyntaxNode.
@@ -132,14 +291,20 @@ impl RustTraceableNode {
                 NodeKind::Function => {
                     let name_node = node.get_child_kind(SyntaxKind::NAME)?;
                     let name = prefix + "." + &name_node.text().to_string();
-                    Some(RustTraceableNode::new(name, location, node_kind))
+                    let mut new_node = RustTraceableNode::new(name, location, node_kind);
+                    // Function name is the context for items declared in the function body.
+                    new_node.context_data = Some(ContextData::new(
+                        Context::from_str(&name_node.text().to_string()),
+                        None,
+                    ));
+                    Some(new_node)
                 }
                 NodeKind::Source => Some(RustTraceableNode::new(
                     "FILE".to_string(),
                     location,
                     node_kind,
                 )),
-                NodeKind::Struct => {
+                NodeKind::Struct | NodeKind::Enum | NodeKind::Macro => {
                     let name_node = node.get_child_kind(SyntaxKind::NAME)?;
                     let name = prefix + "." + &name_node.text().to_string();
                     Some(RustTraceableNode::new(name, location, node_kind))
@@ -154,7 +319,22 @@ impl RustTraceableNode {
                 NodeKind::Trait => {
                     let name_node = node.get_child_kind(SyntaxKind::NAME)?;
                     let name = name_node.text().to_string();
-                    Some(RustTraceableNode::new(name, location, NodeKind::Trait))
+                    let mut new_node = RustTraceableNode::new(name, location, NodeKind::Trait);
+                    // Trait name is the context for default methods.
+                    new_node.context_data = Some(ContextData::new(
+                        Context::from_str(&name_node.text().to_string()),
+                        None,
+                    ));
+                    Some(new_node)
+                }
+                NodeKind::MacroInvocation => {
+                    // Named after the last segment of the macro path, e.g. `item` for
+                    // `paste::item`.
+                    let path_node = node.get_child_kind(SyntaxKind::PATH)?;
+                    let path = path_node.text().to_string();
+                    let macro_name = path.rsplit("::").next()?.trim();
+                    let name = prefix + "." + macro_name;
+                    Some(RustTraceableNode::new(name, location, node_kind))
                 }
                 _ => None,
             }
@@ -166,9 +346,16 @@ impl RustTraceableNode {
     /// Constructs a new RTN from an IMPL SyntaxNode.
     ///
     /// Constructs a new RustTraceableNode from a given ra_ap_syntax SyntaxNode of IMPL SyntaxKind.
-    /// The impl node is searched for path nodes, defining which struct is being implemented for,
-    /// and optionally which trait is being implemented.
+    /// The impl node is searched for type nodes, defining which type is being implemented for,
+    /// and optionally which trait is being implemented. Besides paths, the target can be any type,
+    /// e.g. a tuple `(u8, u8)` or an array `[u8; 4]`, which is then used as context as written.
+    /// Only node children are considered, so the `unsafe` keyword of unsafe impls has no effect.
     /// This information is converted to context data that can be used while parsing enclosed nodes.
+    /// Negative impls (`impl !Trait for Struct`) can not contain items and produce a context node
+    /// without context data. Blanket impls (`impl<T> Trait for T`) use the trait as context, as
+    /// the target is only a generic parameter.
+    /// The context node is named after the impl header, e.g. `impl Trait for Struct` or
+    /// `impl Struct`, to keep the tree legible when it is inspected.
     ///
     /// ### Parameters
     /// * `node` - SyntaxNode that should be parsed to a context RTN.
@@ -176,42 +363,51 @@ impl RustTraceableNode {
     /// ### Returns
     /// Some RustTraceableNode if parsing was sucessful, None otherwise.
     fn from_impl_node(node: &SyntaxNode) -> Option<Self> {
-        // Get target (the struct the impl is for) and optional trait that gets implemented.
-        let path_nodes = node.get_children_kind(SyntaxKind::PATH_TYPE);
+        // Get target (the type the impl is for) and optional trait that gets implemented.
+        let type_nodes: Vec<SyntaxNode> = node
+            .children()
+            .filter(|child| TYPE_KINDS.contains(&child.kind()))
+            .collect();
+        let negative = !node.get_tokens_kind(SyntaxKind::BANG).is_empty();
+
+        let mut new_node = RustTraceableNode::new(
+            impl_name(node, &type_nodes, negative),
+            FileReference::new_default(),
+            NodeKind::Context,
+        );
+
+        // Negative impls have no items, no context is needed.
+        if negative {
+            return Some(new_node);
+        }
 
         // Either impl STRUCTNAME or impl TRAITNAME for STRUCTNAME.
-        if path_nodes.len() == 2 {
-            // Expect the for kw to be present when a trait is implemented (2 path nodes).
+        if type_nodes.len() == 2 {
+            // Expect the for kw to be present when a trait is implemented (2 type nodes).
             let for_kw = node.get_tokens_kind(SyntaxKind::FOR_KW);
             if for_kw.is_empty() {
                 None
             } else {
                 // Parse to context data.
-                let traitref = path_nodes[0].text().to_string();
-                let structref = path_nodes[1].text().to_string();
-                let impl_data = ContextData::new(Context::from_str(&structref), Some(traitref));
-                let mut new_node = RustTraceableNode::new(
-                    "Impl".to_string(),
-                    FileReference::new_default(),
-                    NodeKind::Context,
-                );
+                let traitref = type_text(&type_nodes[0]);
+                let structref = target_type_text(node, &type_nodes[1]);
+                let impl_data = if is_type_param(node, &structref) {
+                    // Blanket impl, name items after the trait.
+                    ContextData::new(Context::from_str(&traitref), Some(traitref))
+                } else {
+                    ContextData::new(Context::from_str(&structref), Some(traitref))
+                };
                 new_node.context_data = Some(impl_data);
                 Some(new_node)
             }
-        } else if path_nodes.len() == 1 {
+        } else if type_nodes.len() == 1 {
             // Parse to context data.
-            let structref = path_nodes[0].text().to_string();
+            let structref = target_type_text(node, &type_nodes[0]);
             let impl_data = ContextData::new(Context::from_str(&structref), None);
-            let mut new_node = RustTraceableNode::new(
-                "Impl".to_string(),
-                FileReference::new_default(),
-                NodeKind::Context,
-            );
             new_node.context_data = Some(impl_data);
             Some(new_node)
         } else {
-            // No path nodes or 3+, fail parsing.
-            println!("WARNING: Malformed impl node. Continuing...");
+            // No type nodes or 3+, fail parsing.
             None
         }
     }
@@ -228,7 +424,7 @@ impl RustTraceableNode {
     /// ### Returns
     /// Some RustTraceableNode if parsing was sucessful, None otherwise.
     fn from_module_node(node: &SyntaxNode) -> Option<Self> {
-        let name_node = node.get_child_kind(SyntaxKind::NAME).unwrap();
+        let name_node = node.get_child_kind(SyntaxKind::NAME)?;
         let mut new_node = RustTraceableNode::new(
             name_node.text().to_string(),
             FileReference::new_default(),
@@ -275,49 +471,342 @@ impl RustTraceableNode {
         self.children.push(child);
     }
 
+    /// Finds a node by its name.
+    ///
+    /// Searches this node and recursively all of its children, including context nodes, for a
+    /// node with the given name. The name is the tag of the node without the tag prefix.
+    ///
+    /// ### Parameters
+    /// * `name` - Name of the node to find, e.g. `main.Foo.bar`.
+    ///
+    /// ### Returns
+    /// Some reference to the first node with the given name, None if no such node exists.
+    pub fn find_by_name(&self, name: &str) -> Option<&RustTraceableNode> {
+        if self.name == name {
+            Some(self)
+        } else {
+            self.children.iter().find_map(|c| c.find_by_name(name))
+        }
+    }
+
+    /// Sets the file of the location of this node and recursively all of its children.
+    ///
+    /// The file is stored with forward slashes as separators, regardless of the platform.
+    ///
+    /// ### Parameters
+    /// * `filename` - File to locate the nodes in, e.g. a path relative to the repository root.
+    pub fn set_filename(&mut self, filename: &str) {
+        self.location.filename = forward_slashes(filename);
+        for child in self.children.iter_mut() {
+            child.set_filename(filename);
+        }
+    }
+
+    /// Finds all untraced nodes of the given kinds.
+    ///
+    /// Searches this node and recursively all of its children for nodes without references and
+    /// without justifications. Nodes exempt via a `lobster-nocheck` comment are skipped.
+    ///
+    /// ### Parameters
+    /// * `kinds` - NodeKinds that are required to be traced.
+    ///
+    /// ### Returns
+    /// References to the untraced nodes, in the order of the tree.
+    pub fn find_untraced(&self, kinds: &[NodeKind]) -> Vec<&RustTraceableNode> {
+        self.find_required(kinds)
+            .into_iter()
+            .filter(|node| !node.is_traced())
+            .collect()
+    }
+
+    /// Finds all nodes of the given kinds that are required to be traced.
+    ///
+    /// Searches this node and recursively all of its children for nodes of the given kinds,
+    /// traced or not. Nodes exempt via a `lobster-nocheck` comment are skipped.
+    ///
+    /// ### Parameters
+    /// * `kinds` - NodeKinds that are required to be traced.
+    ///
+    /// ### Returns
+    /// References to the nodes, in the order of the tree.
+    pub fn find_required(&self, kinds: &[NodeKind]) -> Vec<&RustTraceableNode> {
+        let mut required = Vec::new();
+        if kinds.contains(&self.kind) && !self.nocheck {
+            required.push(self);
+        }
+        for child in self.children.iter() {
+            required.append(&mut child.find_required(kinds));
+        }
+        required
+    }
+
+    /// Checks if the node is traced.
+    ///
+    /// ### Returns
+    /// true if the node has references or justifications in any direction.
+    pub fn is_traced(&self) -> bool {
+        !(self.refs.is_empty()
+            && self.just_up.is_empty()
+            && self.just_down.is_empty()
+            && self.just_global.is_empty())
+    }
+
+    /// Adds the references, justifications and nocheck flag of another node to the node.
+    ///
+    /// ### Parameters
+    /// * `other` - Node whose annotations are added.
+    pub(crate) fn merge_annotations(&mut self, other: RustTraceableNode) {
+        self.refs.extend(other.refs);
+        self.just_up.extend(other.just_up);
+        self.just_down.extend(other.just_down);
+        self.just_global.extend(other.just_global);
+        self.nocheck |= other.nocheck;
+    }
+
     /// Converst to lobster format and adds itselfs to the items.
     ///
     /// Converts the RustTraceableNode to the lobster common interchange format.
-    /// This is either done by converting the node itself (done via the JsonValue::From
-    /// implementation), or by converting and adding all of the nodes children, depending on
-    /// node kind.
+    /// This is either done by converting the node itself (done via to_lobster_item), or by
+    /// converting and adding all of the nodes children, depending on node kind. Functions are
+    /// converted together with the items nested in their body.
+    /// The parent tag of an item is the tag of its scope, e.g. the struct an impl block is for,
+    /// if such an item is emitted. Otherwise it is the tag of the nearest emitted item enclosing
+    /// it, or null if there is none, so that every parent tag refers to an emitted item.
+    ///
+    /// ### Parameters
+    /// * `options` - OutputOptions to apply to the conversion.
     ///
     /// ### Returns
     /// Vector of JsonValues, containing either its own representation and/or the childs
     /// representations.
-    pub(crate) fn to_lobster(&self) -> Vec<JsonValue> {
-        match self.kind {
-            NodeKind::Source => self.children.iter().flat_map(|c| c.to_lobster()).collect(),
-            NodeKind::Function => {
-                vec![JsonValue::from(self)]
+    pub fn to_lobster(&self, options: &OutputOptions) -> Vec<JsonValue> {
+        let mut items = self.to_lobster_in(options, None, &[]);
+        // The scope is looked up by the full names of the nodes, as truncated names may be empty.
+        let tags: HashMap<&str, JsonValue> = items
+            .iter()
+            .map(|(node, item)| (node.name.as_str(), item["tag"].clone()))
+            .collect();
+        for (node, item) in items.iter_mut() {
+            let scope_tag = tags.get(node.scope.to_str().as_str());
+            if let Some(scope_tag) = scope_tag.filter(|_| node.scope.depth() > 1) {
+                item["parent_tag"] = scope_tag.clone();
             }
-            NodeKind::Struct => {
-                vec![JsonValue::from(self)]
+        }
+        items.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Converts to lobster format with the tag of the nearest emitted ancestor, if any.
+    ///
+    /// Nodes that are not emitted, e.g. impl blocks, unannotated modules or nodes of skipped
+    /// kinds, hand the tag of their own nearest emitted ancestor down to their children.
+    /// The `#[cfg(...)]` conditions of a node apply to all of its children, e.g. the conditions
+    /// of an impl block to its methods, they are handed down as well.
+    ///
+    /// ### Parameters
+    /// * `options` - OutputOptions to apply to the conversion.
+    /// * `ancestor_tag` - Tag of the nearest emitted item enclosing the node.
+    /// * `inherited_cfg` - Conditions of the enclosing nodes.
+    ///
+    /// ### Returns
+    /// Vector of JsonValues, containing either its own representation and/or the childs
+    /// representations, each with the node it represents.
+    fn to_lobster_in(
+        &self,
+        options: &OutputOptions,
+        ancestor_tag: Option<&JsonValue>,
+        inherited_cfg: &[String],
+    ) -> Vec<(&RustTraceableNode, JsonValue)> {
+        let mut cfg = inherited_cfg.to_vec();
+        cfg.extend(
+            self.cfg
+                .iter()
+                .filter(|c| !inherited_cfg.contains(c))
+                .cloned(),
+        );
+        let item = match self.kind {
+            NodeKind::Source | NodeKind::Context | NodeKind::Trait => None,
+            _ => self.to_filtered_lobster_item(options, ancestor_tag, &cfg),
+        };
+        // Children of a node that is not emitted belong to its nearest emitted ancestor.
+        let child_ancestor_tag = item.as_ref().map(|item| &item["tag"]).or(ancestor_tag);
+        // Annotated fields and variants, nested items in function bodies and the items of
+        // modules follow the node itself.
+        let children: Vec<(&RustTraceableNode, JsonValue)> = self
+            .children
+            .iter()
+            .flat_map(|c| c.to_lobster_in(options, child_ancestor_tag, &cfg))
+            .collect();
+        item.map(|item| (self, item))
+            .into_iter()
+            .chain(children)
+            .collect()
+    }
+
+    /// Converts the test functions to lobster activities.
+    ///
+    /// Searches the node and recursively all of its children for test functions.
+    /// Parameterized tests with multiple `#[test_case(...)]` attributes are emitted as one
+    /// activity per case, named after the description of the case, e.g. `main.adds[small]`.
+    ///
+    /// ### Parameters
+    /// * `options` - OutputOptions to apply to the conversion.
+    ///
+    /// ### Returns
+    /// Vector of JsonValues in the form of activity items, in the order of the tree.
+    pub fn to_lobster_activities(&self, options: &OutputOptions) -> Vec<JsonValue> {
+        let mut items = Vec::new();
+        if self.is_test {
+            if self.test_cases.len() > 1 {
+                for case in self.test_cases.iter() {
+                    items.push(self.to_lobster_activity(options, Some(case)));
+                }
+            } else {
+                items.push(self.to_lobster_activity(options, None));
             }
-            NodeKind::Context => self.children.iter().flat_map(|c| c.to_lobster()).collect(),
-            _ => vec![],
         }
+        for child in self.children.iter() {
+            items.append(&mut child.to_lobster_activities(options));
+        }
+        items
     }
-}
 
-/// Implement Display for RustTraceableNode.
-impl Display for RustTraceableNode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(
-            f,
-            "Node {} {} at {}",
-            self.kind.to_str(),
-            self.name,
-            self.location
-        )?;
-        Ok(())
+    /// Convert RTN to a JsonValue in the form of an activity item.
+    ///
+    /// Activity items follow the lobster-act-trace schema and represent the test function, or a
+    /// single case of a parameterized test function.
+    ///
+    /// ### Parameters
+    /// * `options` - OutputOptions to apply to the conversion.
+    /// * `case` - Name of the test case, appended to the name in brackets.
+    ///
+    /// ### Returns
+    /// Json object holding the RTN data as lobster activity.
+    fn to_lobster_activity(&self, options: &OutputOptions, case: Option<&String>) -> JsonValue {
+        let (mut name, _) = self.truncated_name(options.max_context_depth);
+        if let Some(case) = case {
+            name = format!("{}[{}]", name, case);
+        }
+        let location = self.output_location(options);
+        let strings = |values: &Vec<String>| {
+            JsonValue::Array(
+                values
+                    .iter()
+                    .map(|v| JsonValue::String(v.to_string()))
+                    .collect(),
+            )
+        };
+        let mut json_out = JsonValue::Object(Object::new());
+        let _ = json_out.insert("tag", format!("{} {}", options.tag_prefix, name));
+        let _ = json_out.insert("location", JsonValue::from(&location));
+        let _ = json_out.insert("name", name);
+        let _ = json_out.insert("messages", JsonValue::Array(Vec::new()));
+        let _ = json_out.insert("just_up", strings(&self.just_up));
+        let _ = json_out.insert("just_down", strings(&self.just_down));
+        let _ = json_out.insert("just_global", strings(&self.just_global));
+        let _ = json_out.insert("refs", strings(&self.refs));
+        let _ = json_out.insert("framework", "Rust");
+        let _ = json_out.insert("kind", "Test");
+        let _ = json_out.insert("status", JsonValue::Null);
+        json_out
+    }
+
+    /// Converts the nodes of the requirement kinds to lobster requirements.
+    ///
+    /// Searches the node and recursively all of its children for nodes of the kinds given by
+    /// the requirement_kinds of the options.
+    ///
+    /// ### Parameters
+    /// * `options` - OutputOptions to apply to the conversion.
+    ///
+    /// ### Returns
+    /// Vector of JsonValues in the form of requirement items, in the order of the tree.
+    pub fn to_lobster_requirements(&self, options: &OutputOptions) -> Vec<JsonValue> {
+        let mut items = Vec::new();
+        if options.requirement_kinds.contains(&self.kind) {
+            items.push(self.to_lobster_requirement(options));
+        }
+        for child in self.children.iter() {
+            items.append(&mut child.to_lobster_requirements(options));
+        }
+        items
+    }
+
+    /// Convert RTN to a JsonValue in the form of a requirement item.
+    ///
+    /// Requirement items follow the lobster-req-trace schema. They are tagged in the `req`
+    /// namespace, so implementation items can reference them like any other requirement.
+    ///
+    /// ### Parameters
+    /// * `options` - OutputOptions to apply to the conversion.
+    ///
+    /// ### Returns
+    /// Json object holding the RTN data as lobster requirement.
+    fn to_lobster_requirement(&self, options: &OutputOptions) -> JsonValue {
+        let (name, _) = self.truncated_name(options.max_context_depth);
+        let location = self.output_location(options);
+        let strings = |values: &Vec<String>| {
+            JsonValue::Array(
+                values
+                    .iter()
+                    .map(|v| JsonValue::String(v.to_string()))
+                    .collect(),
+            )
+        };
+
+        let mut json_out = JsonValue::Object(Object::new());
+        let _ = json_out.insert("tag", format!("req {}", name));
+        let _ = json_out.insert("location", JsonValue::from(&location));
+        let _ = json_out.insert("name", name);
+        let _ = json_out.insert("messages", JsonValue::Array(Vec::new()));
+        let _ = json_out.insert("just_up", strings(&self.just_up));
+        let _ = json_out.insert("just_down", strings(&self.just_down));
+        let _ = json_out.insert("just_global", strings(&self.just_global));
+        let _ = json_out.insert("refs", strings(&self.refs));
+        let _ = json_out.insert("framework", "Rust");
+        let _ = json_out.insert("kind", options.kind_str(self.kind));
+        let _ = json_out.insert("text", JsonValue::Null);
+        let _ = json_out.insert("status", JsonValue::Null);
+        json_out
+    }
+
+    /// Convert RTN to a JsonValue if it passes the ref filter.
+    ///
+    /// Without ref filter, every node passes. With ref filter, only nodes with at least one
+    /// reference starting with the filter prefix pass. All references of passing nodes are kept.
+    /// Nodes of skipped kinds never pass.
+    ///
+    /// ### Parameters
+    /// * `options` - OutputOptions to apply to the conversion.
+    /// * `ancestor_tag` - Tag of the nearest emitted item enclosing the node.
+    /// * `cfg` - Conditions of the node, including the conditions of the enclosing nodes.
+    ///
+    /// ### Returns
+    /// Some JsonValue holding the RTN data if the node passes the filter, None otherwise.
+    fn to_filtered_lobster_item(
+        &self,
+        options: &OutputOptions,
+        ancestor_tag: Option<&JsonValue>,
+        cfg: &[String],
+    ) -> Option<JsonValue> {
+        // Nodes emitted as requirements are no implementation items.
+        if options.requirement_kinds.contains(&self.kind) {
+            return None;
+        }
+        // Only the node itself is skipped, its children are converted independently.
+        if options.skip_kinds.contains(&self.kind) {
+            return None;
+        }
+        let passes = match &options.ref_filter {
+            None => true,
+            Some(prefix) => self.refs.iter().any(|r| {
+                r.split_once(' ')
+                    .map_or(r.as_str(), |(_, id)| id)
+                    .starts_with(prefix.as_str())
+            }),
+        };
+        passes.then(|| self.to_lobster_item(options, ancestor_tag, cfg))
     }
-}
 
-/// Implement JsonValue::from(node: &RustTraceableNode)
-///
-/// This allows conversion from a RTN to a JsonValue.
-impl From<&RustTraceableNode> for JsonValue {
     /// Convert RTN to a JsonValue.
     ///
     /// Parse a JsonValue from a RustTraceableNode.
@@ -327,55 +816,266 @@ impl From<&RustTraceableNode> for JsonValue {
     /// lists.)
     ///
     /// ### Parameters
-    /// * `node` - RustTraceableNode to convert to JsonValue.
+    /// * `options` - OutputOptions to apply to the conversion.
+    /// * `ancestor_tag` - Tag of the nearest emitted item enclosing the node.
+    /// * `cfg` - Conditions of the node, including the conditions of the enclosing nodes.
     ///
-    /// ### Returns Json object holding the RTN data in lobser common interchange format.
-    fn from(node: &RustTraceableNode) -> JsonValue {
-        // idk if we really want to do this
+    /// ### Returns
+    /// Json object holding the RTN data in lobser common interchange format.
+    fn to_lobster_item(
+        &self,
+        options: &OutputOptions,
+        ancestor_tag: Option<&JsonValue>,
+        cfg: &[String],
+    ) -> JsonValue {
+        let (name, scope) = self.truncated_name(options.max_context_depth);
         let mut json_out = JsonValue::Object(Object::new());
-        let _ = json_out.insert("tag", format!("rust {}", node.name));
-        let _ = json_out.insert("name", node.name.to_string());
-        let _ = json_out.insert("location", JsonValue::from(&node.location));
+        let _ = json_out.insert("tag", format!("{} {}", options.tag_prefix, name));
+        let _ = json_out.insert("name", name);
+        let location = self.output_location(options);
+        let _ = json_out.insert("location", JsonValue::from(&location));
         let _ = json_out.insert("messages", JsonValue::Array(Vec::new()));
         let _ = json_out.insert(
             "just_up",
             JsonValue::Array(
-                node.just
+                self.just_up
+                    .iter()
+                    .map(|j| JsonValue::String(j.to_string()))
+                    .collect(),
+            ),
+        );
+        let _ = json_out.insert(
+            "just_down",
+            JsonValue::Array(
+                self.just_down
+                    .iter()
+                    .map(|j| JsonValue::String(j.to_string()))
+                    .collect(),
+            ),
+        );
+        let _ = json_out.insert(
+            "just_global",
+            JsonValue::Array(
+                self.just_global
                     .iter()
                     .map(|j| JsonValue::String(j.to_string()))
                     .collect(),
             ),
         );
-        let _ = json_out.insert("just_down", JsonValue::Array(Vec::new()));
-        let _ = json_out.insert("just_global", JsonValue::Array(Vec::new()));
         let _ = json_out.insert(
             "refs",
             JsonValue::Array(
-                node.refs
+                self.refs
                     .iter()
                     .map(|r| JsonValue::String(r.to_string()))
                     .collect(),
             ),
         );
+        let _ = json_out.insert(
+            "scope",
+            JsonValue::Array(scope.to_vec().into_iter().map(JsonValue::String).collect()),
+        );
+        let _ = json_out.insert(
+            "parent_tag",
+            ancestor_tag.cloned().unwrap_or(JsonValue::Null),
+        );
         let _ = json_out.insert("language", "Rust");
-        let _ = json_out.insert("kind", node.kind.to_str());
+        let _ = json_out.insert("kind", options.kind_str(self.kind));
+        if let Some(modifiers) = &self.modifiers {
+            let _ = json_out.insert("async", modifiers.is_async);
+            let _ = json_out.insert("const", modifiers.is_const);
+            let _ = json_out.insert("unsafe", modifiers.is_unsafe);
+        }
+        if let Some(symbol) = &self.symbol {
+            let _ = json_out.insert("symbol", symbol.to_string());
+        }
+        if let Some(complexity) = self.complexity.filter(|_| options.complexity) {
+            let mut json_complexity = JsonValue::Object(Object::new());
+            let _ = json_complexity.insert("parameters", complexity.parameters);
+            let _ = json_complexity.insert("generic_params", complexity.generic_params);
+            if let Some(statements) = complexity.statements {
+                let _ = json_complexity.insert("statements", statements);
+            }
+            let _ = json_out.insert("complexity", json_complexity);
+        }
+        if let Some(message) = &self.message {
+            let _ = json_out.insert("message", message.to_string());
+        }
+        if self.non_exhaustive {
+            let _ = json_out.insert("non_exhaustive", true);
+        }
+        if !cfg.is_empty() {
+            let _ = json_out.insert(
+                "cfg",
+                JsonValue::Array(cfg.iter().map(|c| JsonValue::String(c.clone())).collect()),
+            );
+        }
         json_out
     }
+
+    /// Get the location of the node as it is emitted.
+    ///
+    /// ### Parameters
+    /// * `options` - OutputOptions to apply to the location.
+    ///
+    /// ### Returns
+    /// The location with 0-based columns or the file as URI, if requested by the options.
+    fn output_location(&self, options: &OutputOptions) -> FileReference {
+        let mut location = self.location.clone();
+        if options.zero_based_columns {
+            location.column = location.column.map(|column| column.saturating_sub(1));
+        }
+        if options.file_uri {
+            location.filename = location.to_file_uri();
+        }
+        location
+    }
+
+    /// Get the name and scope of the node with the scope limited to a maximum depth.
+    ///
+    /// ### Parameters
+    /// * `max_context_depth` - Optional maximum number of namespaces in the scope. Outer
+    ///   namespaces beyond the limit are removed from the name and the scope.
+    ///
+    /// ### Returns
+    /// Tuple of the (possibly truncated) name and scope.
+    fn truncated_name(&self, max_context_depth: Option<usize>) -> (String, Context) {
+        let prefix = format!("{}.", self.scope.to_str());
+        let (Some(max_depth), Some(item_name)) = (
+            max_context_depth.filter(|d| self.scope.depth() > *d),
+            self.name.strip_prefix(&prefix),
+        ) else {
+            return (self.name.clone(), self.scope.clone());
+        };
+        let scope = self.scope.truncate(max_depth);
+        let name = match scope {
+            Context::Empty => item_name.to_string(),
+            _ => format!("{}.{}", scope.to_str(), item_name),
+        };
+        (name, scope)
+    }
+}
+
+/// Implement Display for RustTraceableNode.
+impl Display for RustTraceableNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Node {} {} at {}",
+            self.kind.to_str(),
+            self.name,
+            self.location
+        )?;
+        Ok(())
+    }
+}
+
+/// Options for the conversion of RustTraceableNodes to the lobster common interchange format.
+#[derive(Debug, Clone)]
+pub struct OutputOptions {
+    /// Prefix of the tracing tags, separated from the node name by a space.
+    pub tag_prefix: String,
+    /// Emit 0-based columns instead of 1-based columns.
+    pub zero_based_columns: bool,
+    /// Maximum number of namespaces in names and scopes, outer namespaces are truncated.
+    pub max_context_depth: Option<usize>,
+    /// Only emit items with a reference starting with this prefix.
+    pub ref_filter: Option<String>,
+    /// Kinds of nodes that are emitted as requirements instead of implementation items.
+    pub requirement_kinds: Vec<NodeKind>,
+    /// Emit the file of locations as file URI. The files are expected to be absolute paths.
+    pub file_uri: bool,
+    /// Custom kind strings, keyed by the default kind string, e.g. `Function` -> `function`.
+    pub kind_map: HashMap<String, String>,
+    /// Emit the size of the signature and body of functions as complexity metric.
+    pub complexity: bool,
+    /// Kinds of nodes that are left out of the output. Their children are still emitted.
+    pub skip_kinds: Vec<NodeKind>,
+}
+
+impl OutputOptions {
+    /// Get the kind string of a NodeKind as it is emitted.
+    ///
+    /// ### Parameters
+    /// * `kind` - NodeKind to get the kind string of.
+    ///
+    /// ### Returns
+    /// The custom kind string from the kind map, the default kind string otherwise.
+    pub fn kind_str(&self, kind: NodeKind) -> &str {
+        self.kind_map
+            .get(kind.to_str())
+            .map_or(kind.to_str(), String::as_str)
+    }
+
+    /// Parse a kind map from a JSON object.
+    ///
+    /// The object maps default kind strings to custom kind strings, e.g.
+    /// `{"Function": "function"}`. Kinds not in the object keep their default kind string. Source
+    /// and Module nodes share the kind string `Module`, but only Module nodes are emitted as items.
+    ///
+    /// ### Parameters
+    /// * `text` - JSON text of the kind map.
+    ///
+    /// ### Returns
+    /// The kind map, or an error message if the text is no JSON object, a key is no known kind
+    /// or a value is no non-empty string.
+    pub fn parse_kind_map(text: &str) -> Result<HashMap<String, String>, String> {
+        let value = json::parse(text).map_err(|e| e.to_string())?;
+        if !value.is_object() {
+            return Err("Expected a JSON object".to_string());
+        }
+        let mut kind_map = HashMap::new();
+        for (key, kind_value) in value.entries() {
+            if !NodeKind::ALL.iter().any(|kind| kind.to_str() == key) {
+                return Err(format!("Unknown kind {:?}", key));
+            }
+            match kind_value.as_str() {
+                Some(kind_string) if !kind_string.is_empty() => {
+                    let _ = kind_map.insert(key.to_string(), kind_string.to_string());
+                }
+                _ => return Err(format!("Expected a non-empty string for kind {:?}", key)),
+            }
+        }
+        Ok(kind_map)
+    }
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        OutputOptions {
+            tag_prefix: "rust".to_string(),
+            zero_based_columns: false,
+            max_context_depth: None,
+            ref_filter: None,
+            requirement_kinds: Vec::new(),
+            file_uri: false,
+            kind_map: HashMap::new(),
+            complexity: false,
+            skip_kinds: Vec::new(),
+        }
+    }
 }
 
 /// Holds namespace and optional trait information.
 #[derive(Debug, Clone)]
-pub(crate) struct ContextData {
-    pub(crate) context: Context,
-    pub(crate) _trait_imp: Option<String>,
+pub struct ContextData {
+    /// Context of the items nested in the node, e.g. `Square` for `impl Square`.
+    pub context: Context,
+    /// Path of the implemented trait as written in the impl header, e.g. `shapes::Area`.
+    pub(crate) trait_imp: Option<String>,
+    /// Name of the implemented trait item, resolved from the path of trait_imp, e.g.
+    /// `main.shapes.Area` for `impl shapes::Area for Square` in main.rs.
+    pub(crate) trait_path: Option<String>,
+    /// References of the annotated methods of a trait definition, by method name.
+    pub(crate) method_refs: HashMap<String, Vec<String>>,
 }
 
 impl ContextData {
     /// Construct new context data.
     ///
     /// ### Parameters
-    /// * `context` - Context to represent some enclosing namespace, be it a local module name or
-    ///   the target struct name of an impl block.
+    /// * `context` - Context to represent some enclosing namespace, be it a local module name,
+    ///   the name of a function declaring nested items or the target struct name of an impl block.
     /// * `trait_imp` - Optional name of the trait being implemented (for impl blocks that implement
     ///   a trait for a target struct).
     ///
@@ -384,11 +1084,333 @@ impl ContextData {
     fn new(context: Context, trait_imp: Option<String>) -> Self {
         ContextData {
             context,
-            _trait_imp: trait_imp,
+            trait_imp,
+            trait_path: None,
+            method_refs: HashMap::new(),
+        }
+    }
+}
+
+/// Qualifiers of a function signature.
+///
+/// Emitted as the boolean fields `async`, `const` and `unsafe` of function items, so that e.g.
+/// unsafe functions can be reviewed against safety requirements.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FnModifiers {
+    /// The function is declared `async`.
+    pub is_async: bool,
+    /// The function is declared `const`.
+    pub is_const: bool,
+    /// The function is declared `unsafe`.
+    pub is_unsafe: bool,
+}
+
+impl FnModifiers {
+    /// Read the qualifiers of a function.
+    ///
+    /// The qualifier keywords are direct token children of the FN node.
+    ///
+    /// ### Parameters
+    /// * `fn_node` - SyntaxNode of kind FN.
+    ///
+    /// ### Returns
+    /// The qualifiers of the function.
+    pub(crate) fn from_fn_node(fn_node: &SyntaxNode) -> Self {
+        FnModifiers {
+            is_async: !fn_node.get_tokens_kind(SyntaxKind::ASYNC_KW).is_empty(),
+            is_const: !fn_node.get_tokens_kind(SyntaxKind::CONST_KW).is_empty(),
+            is_unsafe: !fn_node.get_tokens_kind(SyntaxKind::UNSAFE_KW).is_empty(),
+        }
+    }
+}
+
+/// Size of a function signature and body.
+///
+/// A lightweight static metric to prioritize reviews, not the cyclomatic complexity.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FnComplexity {
+    /// Number of parameters, including a `self` parameter.
+    pub parameters: usize,
+    /// Number of lifetime, type and const generic parameters.
+    pub generic_params: usize,
+    /// Number of statements at the top level of the body, including items and the tail
+    /// expression. None for functions without body.
+    pub statements: Option<usize>,
+}
+
+impl FnComplexity {
+    /// Count the parameters, generic parameters and statements of a function.
+    ///
+    /// Nested blocks count as one statement, their statements are not counted.
+    ///
+    /// ### Parameters
+    /// * `fn_node` - SyntaxNode of kind FN.
+    ///
+    /// ### Returns
+    /// The complexity of the function.
+    pub(crate) fn from_fn_node(fn_node: &SyntaxNode) -> Self {
+        let parameters = fn_node
+            .get_child_kind(SyntaxKind::PARAM_LIST)
+            .map_or(0, |param_list| {
+                param_list
+                    .children()
+                    .filter(|param| {
+                        [SyntaxKind::PARAM, SyntaxKind::SELF_PARAM].contains(&param.kind())
+                    })
+                    .count()
+            });
+        let generic_params = fn_node
+            .get_child_kind(SyntaxKind::GENERIC_PARAM_LIST)
+            .map_or(0, |param_list| param_list.children().count());
+        let statements = fn_node.get_child_kind(SyntaxKind::BLOCK_EXPR).map(|body| {
+            match body.get_child_kind(SyntaxKind::STMT_LIST) {
+                Some(stmt_list) => stmt_list
+                    .children()
+                    .filter(|stmt| SyntaxKind::ATTR != stmt.kind())
+                    .count(),
+                None => 0,
+            }
+        });
+        FnComplexity {
+            parameters,
+            generic_params,
+            statements,
         }
     }
 }
 
+/// SyntaxKinds of the type nodes that can be the target of an impl block.
+const TYPE_KINDS: [SyntaxKind; 10] = [
+    SyntaxKind::PATH_TYPE,
+    SyntaxKind::TUPLE_TYPE,
+    SyntaxKind::ARRAY_TYPE,
+    SyntaxKind::SLICE_TYPE,
+    SyntaxKind::REF_TYPE,
+    SyntaxKind::PTR_TYPE,
+    SyntaxKind::FN_PTR_TYPE,
+    SyntaxKind::NEVER_TYPE,
+    SyntaxKind::DYN_TRAIT_TYPE,
+    SyntaxKind::PAREN_TYPE,
+];
+
+/// Get the name of an impl block context node.
+///
+/// The name is reconstructed from the types of the impl header, without generic parameters, e.g.
+/// `impl Trait for Struct`, `impl !Send for Struct` or `impl Struct`. The target type is rendered
+/// as in target_type_text.
+///
+/// ### Parameters
+/// * `impl_node` - SyntaxNode of kind IMPL.
+/// * `type_nodes` - Type nodes of the impl header, the trait (if any) followed by the target.
+/// * `negative` - Whether the impl is a negative impl.
+///
+/// ### Returns
+/// Name of the impl block, `impl` if the header has no or too many types.
+fn impl_name(impl_node: &SyntaxNode, type_nodes: &[SyntaxNode], negative: bool) -> String {
+    match type_nodes {
+        [trait_type, target_type] => format!(
+            "impl {}{} for {}",
+            if negative { "!" } else { "" },
+            type_text(trait_type),
+            target_type_text(impl_node, target_type)
+        ),
+        [target_type] => format!("impl {}", target_type_text(impl_node, target_type)),
+        _ => "impl".to_string(),
+    }
+}
+
+/// Get the text of the target type of an impl block.
+///
+/// The target type is rendered as in type_text, except that the generic arguments of the last
+/// path segment are kept if they are concrete, i.e. if none of them refers to a generic parameter
+/// of the impl block. Lifetime arguments are dropped. Thus `impl<const N: usize> Buffer<N>` is
+/// rendered as `Buffer`, while `impl Buffer<4>` and `impl Buffer<8>` are rendered as `Buffer<4>`
+/// and `Buffer<8>`, so that the items of different specializations get distinct names.
+///
+/// ### Parameters
+/// * `impl_node` - SyntaxNode of kind IMPL.
+/// * `type_node` - Target type node of the impl header.
+///
+/// ### Returns
+/// String representation of the target type.
+fn target_type_text(impl_node: &SyntaxNode, type_node: &SyntaxNode) -> String {
+    let text = type_text(type_node);
+    match concrete_generic_args(impl_node, type_node) {
+        Some(args) => format!("{}<{}>", text, args.join(", ")),
+        None => text,
+    }
+}
+
+/// Get the concrete generic arguments of a type node.
+///
+/// References are resolved to the referenced type. Only the generic arguments of the last segment
+/// of a path type are considered, lifetime arguments are ignored.
+///
+/// ### Parameters
+/// * `impl_node` - SyntaxNode of kind IMPL, whose generic parameters are not concrete.
+/// * `type_node` - SyntaxNode of one of the TYPE_KINDS.
+///
+/// ### Returns
+/// Some texts of the generic arguments if there are any and none refers to a generic parameter of
+/// the impl block, None otherwise.
+fn concrete_generic_args(impl_node: &SyntaxNode, type_node: &SyntaxNode) -> Option<Vec<String>> {
+    match type_node.kind() {
+        SyntaxKind::REF_TYPE => {
+            let referenced_type = type_node
+                .children()
+                .find(|child| TYPE_KINDS.contains(&child.kind()))?;
+            concrete_generic_args(impl_node, &referenced_type)
+        }
+        SyntaxKind::PATH_TYPE => {
+            let arg_list = type_node
+                .get_child_kind(SyntaxKind::PATH)?
+                .get_child_kind(SyntaxKind::PATH_SEGMENT)?
+                .get_child_kind(SyntaxKind::GENERIC_ARG_LIST)?;
+            let args: Vec<SyntaxNode> = arg_list
+                .children()
+                .filter(|arg| arg.kind() != SyntaxKind::LIFETIME_ARG)
+                .collect();
+            let params = generic_param_names(impl_node);
+            let refers_to_param = args.iter().any(|arg| {
+                arg.descendants_with_tokens().any(|element| {
+                    element.kind() == SyntaxKind::IDENT
+                        && params.iter().any(|param| element.to_string() == *param)
+                })
+            });
+            if args.is_empty() || refers_to_param {
+                None
+            } else {
+                Some(args.iter().map(|arg| arg.text().to_string()).collect())
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Get the names of the type and const parameters of an impl block.
+///
+/// ### Parameters
+/// * `impl_node` - SyntaxNode of kind IMPL.
+///
+/// ### Returns
+/// Names of the type and const parameters, empty if the impl block is not generic.
+fn generic_param_names(impl_node: &SyntaxNode) -> Vec<String> {
+    impl_node
+        .get_child_kind(SyntaxKind::GENERIC_PARAM_LIST)
+        .map(|param_list| {
+            param_list
+                .children()
+                .filter(|param| {
+                    matches!(
+                        param.kind(),
+                        SyntaxKind::TYPE_PARAM | SyntaxKind::CONST_PARAM
+                    )
+                })
+                .filter_map(|param| param.get_child_kind(SyntaxKind::NAME))
+                .map(|param_name| param_name.text().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Get the text of a type node.
+///
+/// Paths are rendered without generic arguments (see path_type_text). References are rendered
+/// without lifetime, e.g. `&mut Foo` for `&'a mut Foo`, so that impl blocks for a type and for
+/// references to it get distinct namespaces. All other types are rendered as written, e.g.
+/// `(u8, u8)` or `[u8; 4]`.
+///
+/// ### Parameters
+/// * `type_node` - SyntaxNode of one of the TYPE_KINDS.
+///
+/// ### Returns
+/// String representation of the type.
+fn type_text(type_node: &SyntaxNode) -> String {
+    match type_node.kind() {
+        SyntaxKind::PATH_TYPE => path_type_text(type_node),
+        SyntaxKind::REF_TYPE => match type_node
+            .children()
+            .find(|child| TYPE_KINDS.contains(&child.kind()))
+        {
+            Some(referenced_type) => {
+                let is_mut = type_node
+                    .children_with_tokens()
+                    .any(|child| child.kind() == SyntaxKind::MUT_KW);
+                let reference = if is_mut { "&mut " } else { "&" };
+                reference.to_string() + &type_text(&referenced_type)
+            }
+            None => type_node.text().to_string(),
+        },
+        _ => type_node.text().to_string(),
+    }
+}
+
+/// Get the text of a PATH_TYPE node without generic arguments.
+///
+/// Renders the path of the given PATH_TYPE node segment by segment.
+/// Generic arguments are dropped, so that e.g. `Foo<'a>`, `Foo<T>` and `Foo<N>` are all rendered
+/// as `Foo`. This keeps names of items in generic impl blocks, including lifetime and const
+/// generic ones, identical to the names of items in plain impl blocks.
+///
+/// ### Parameters
+/// * `path_type_node` - SyntaxNode of kind PATH_TYPE.
+///
+/// ### Returns
+/// String representation of the path.
+fn path_type_text(path_type_node: &SyntaxNode) -> String {
+    match path_type_node.get_child_kind(SyntaxKind::PATH) {
+        Some(path_node) => path_text(&path_node),
+        None => path_type_node.text().to_string(),
+    }
+}
+
+/// Get the text of a PATH node without generic arguments.
+///
+/// Paths are nested, with the qualifier being a PATH child of the path node and the last segment
+/// being a PATH_SEGMENT child. The qualifier is resolved recursively.
+///
+/// ### Parameters
+/// * `path_node` - SyntaxNode of kind PATH.
+///
+/// ### Returns
+/// String representation of the path, segments separated by `::`.
+fn path_text(path_node: &SyntaxNode) -> String {
+    let mut text = match path_node.get_child_kind(SyntaxKind::PATH) {
+        Some(qualifier) => path_text(&qualifier) + "::",
+        None => String::new(),
+    };
+
+    if let Some(segment_node) = path_node.get_child_kind(SyntaxKind::PATH_SEGMENT) {
+        // Only the name of the segment is kept, generic arguments are dropped.
+        match segment_node.get_child_kind(SyntaxKind::NAME_REF) {
+            Some(name_ref) => text.push_str(&name_ref.text().to_string()),
+            None => text.push_str(&segment_node.text().to_string()),
+        }
+    }
+    text
+}
+
+/// Check if a name refers to a type parameter of an impl block.
+///
+/// ### Parameters
+/// * `impl_node` - SyntaxNode of kind IMPL.
+/// * `name` - Type name to check.
+///
+/// ### Returns
+/// true if the impl block declares a type parameter with the given name.
+fn is_type_param(impl_node: &SyntaxNode, name: &str) -> bool {
+    impl_node
+        .get_child_kind(SyntaxKind::GENERIC_PARAM_LIST)
+        .map(|param_list| {
+            param_list
+                .get_children_kind(SyntaxKind::TYPE_PARAM)
+                .iter()
+                .filter_map(|type_param| type_param.get_child_kind(SyntaxKind::NAME))
+                .any(|param_name| param_name.text() == name)
+        })
+        .unwrap_or(false)
+}
+
 /// Convert SyntaxKind to NodeKind.
 ///
 /// Converts the SyntaxKind of a SyntaxNode from the ra_ap_syntax crate to the corresponding
@@ -408,6 +1430,8 @@ fn syntax_kind_to_node_kind(kind: SyntaxKind) -> Option<NodeKind> {
         SyntaxKind::TRAIT => Some(NodeKind::Trait),
         SyntaxKind::IMPL => Some(NodeKind::Context),
         SyntaxKind::MODULE => Some(NodeKind::Context),
+        SyntaxKind::MACRO_CALL => Some(NodeKind::MacroInvocation),
+        SyntaxKind::MACRO_DEF => Some(NodeKind::Macro),
         _ => None,
     }
 }
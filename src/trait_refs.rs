@@ -0,0 +1,137 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Linking of trait method references across the parsed files.
+//!
+//! Methods of trait implementations inherit the references of the corresponding method of the
+//! trait definition, so that an implementation is traced to the requirements of the trait
+//! without repeating the annotations. Traits and implementations are matched across all parsed
+//! files, independent of the order they are defined in.
+
+use std::collections::HashMap;
+
+use crate::traceable_node::{NodeKind, RustTraceableNode};
+
+/// Let the methods of trait implementations inherit the references of the trait methods.
+///
+/// Traits are identified by the name of the trait item, e.g. `main.shapes.Area`, which the
+/// implemented trait path is resolved to while parsing. If no trait of that name exists, e.g.
+/// for traits imported in an inline module, a trait with the same last segment is used if it is
+/// the only one of that name. Inherited references follow the own references of the method.
+///
+/// ### Parameters
+/// * `modules` - Root nodes of all parsed files.
+pub fn link_trait_method_refs(modules: &mut [RustTraceableNode]) {
+    let mut traits: HashMap<String, HashMap<String, Vec<String>>> = HashMap::new();
+    for module in modules.iter() {
+        collect_traits(module, &mut traits);
+    }
+    if traits.is_empty() {
+        return;
+    }
+    for module in modules.iter_mut() {
+        inherit_refs(module, &traits);
+    }
+}
+
+/// Recursively collect the method references of the traits in a node.
+///
+/// ### Parameters
+/// * `node` - Node to search for trait definitions.
+/// * `traits` - Method references by method name, by trait name.
+fn collect_traits(
+    node: &RustTraceableNode,
+    traits: &mut HashMap<String, HashMap<String, Vec<String>>>,
+) {
+    if let (NodeKind::Trait, Some(context_data)) = (node.kind, &node.context_data) {
+        let _ = traits.insert(node.name.clone(), context_data.method_refs.clone());
+    }
+    for child in node.children.iter() {
+        collect_traits(child, traits);
+    }
+}
+
+/// Find the method references of the trait an impl block implements.
+///
+/// ### Parameters
+/// * `trait_path` - Resolved name of the implemented trait.
+/// * `traits` - Method references by method name, by trait name.
+///
+/// ### Returns
+/// Some method references of the trait, None if the trait is unknown or ambiguous.
+fn find_trait<'a>(
+    trait_path: &str,
+    traits: &'a HashMap<String, HashMap<String, Vec<String>>>,
+) -> Option<&'a HashMap<String, Vec<String>>> {
+    if let Some(method_refs) = traits.get(trait_path) {
+        return Some(method_refs);
+    }
+    let trait_name = trait_path.rsplit('.').next()?;
+    let mut candidates = traits
+        .iter()
+        .filter(|(name, _)| name.rsplit('.').next() == Some(trait_name));
+    match (candidates.next(), candidates.next()) {
+        (Some((_, method_refs)), None) => Some(method_refs),
+        _ => None,
+    }
+}
+
+/// Recursively add the references of the trait methods to the methods of trait implementations.
+///
+/// ### Parameters
+/// * `node` - Node to search for impl blocks.
+/// * `traits` - Method references by method name, by trait name.
+fn inherit_refs(
+    node: &mut RustTraceableNode,
+    traits: &HashMap<String, HashMap<String, Vec<String>>>,
+) {
+    let method_refs = node
+        .context_data
+        .as_ref()
+        .filter(|_| NodeKind::Context == node.kind)
+        .and_then(|context_data| context_data.trait_path.as_deref())
+        .and_then(|trait_path| find_trait(trait_path, traits));
+    if let Some(method_refs) = method_refs {
+        for method in node
+            .children
+            .iter_mut()
+            .filter(|child| NodeKind::Function == child.kind)
+        {
+            let method_name = method.name.rsplit('.').next().unwrap_or(&method.name);
+            for reference in method_refs.get(method_name).into_iter().flatten() {
+                if !method.refs.contains(reference) {
+                    method.refs.push(reference.clone());
+                }
+            }
+        }
+    }
+    for child in node.children.iter_mut() {
+        inherit_refs(child, traits);
+    }
+}
@@ -0,0 +1,366 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Utility functions to inspect attributes of items.
+
+use ra_ap_syntax::{ast, AstToken, SyntaxKind, SyntaxNode, SyntaxToken};
+
+use crate::syntax_extensions::Searchable;
+
+/// Get the path of an attribute.
+///
+/// Extracts the path of the META node of the attribute node, e.g. `no_mangle` for
+/// `#[no_mangle]` or `export_name` for `#[export_name = "symbol"]`. The META node of an unsafe
+/// attribute holds the UNSAFE_KW token and the parenthesized path, so the path is `no_mangle` for
+/// `#[unsafe(no_mangle)]` as well.
+///
+/// ### Parameters
+/// * `attr_node` - The attribute node to inspect.
+///
+/// ### Returns
+/// Some(String) with the attribute path, None if the attribute has no path.
+pub(crate) fn attribute_path(attr_node: &SyntaxNode) -> Option<String> {
+    let meta_node = attr_node.get_child_kind(SyntaxKind::META)?;
+    let path_node = meta_node.get_child_kind(SyntaxKind::PATH)?;
+    Some(path_node.text().to_string())
+}
+
+/// Get the string literal token assigned to an attribute.
+///
+/// ### Parameters
+/// * `attr_node` - The attribute node to inspect.
+///
+/// ### Returns
+/// Some SyntaxToken of kind STRING, None if no string literal is assigned.
+fn attribute_string_token(attr_node: &SyntaxNode) -> Option<SyntaxToken> {
+    let meta_node = attr_node.get_child_kind(SyntaxKind::META)?;
+    let literal_node = meta_node.get_child_kind(SyntaxKind::LITERAL)?;
+    literal_node
+        .get_tokens_kind(SyntaxKind::STRING)
+        .first()
+        .cloned()
+}
+
+/// Get the string value of an attribute.
+///
+/// Extracts the value of the string literal assigned to the attribute, e.g. `symbol` for
+/// `#[export_name = "symbol"]` or `#[export_name = r"symbol"]`. Escape sequences are resolved.
+///
+/// ### Parameters
+/// * `attr_node` - The attribute node to inspect.
+///
+/// ### Returns
+/// Some(String) with the attribute value, None if no valid string value is assigned.
+pub(crate) fn attribute_string_value(attr_node: &SyntaxNode) -> Option<String> {
+    let string_token = ast::String::cast(attribute_string_token(attr_node)?)?;
+    let value = string_token.value().ok()?;
+    Some(value.to_string())
+}
+
+/// Get the text of a doc attribute.
+///
+/// Doc comments can also be written as attribute, e.g. `#[doc = "text"]`, which is common for
+/// generated code.
+///
+/// ### Parameters
+/// * `attr_node` - The attribute node to inspect.
+///
+/// ### Returns
+/// Some(String) with the documentation text, None if the attribute is no doc attribute.
+pub(crate) fn doc_attribute_text(attr_node: &SyntaxNode) -> Option<String> {
+    if Some("doc") != attribute_path(attr_node).as_deref() {
+        return None;
+    }
+    attribute_string_value(attr_node)
+}
+
+/// Get the annotations of an attribute.
+///
+/// Annotations are given via doc attributes (`#[doc = "lobster-trace: ..."]`) or trace attributes
+/// listing the references (`#[lobster::trace(REF, ...)]`). The references of a trace attribute
+/// are converted to one `lobster-trace: REF` annotation each, so that they are parsed like the
+/// annotations of comments.
+///
+/// ### Parameters
+/// * `attr_node` - The attribute node to inspect.
+///
+/// ### Returns
+/// Annotation texts in the order of the attribute, empty if the attribute holds no annotations.
+pub(crate) fn attribute_annotations(attr_node: &SyntaxNode) -> Vec<String> {
+    if let Some(doc) = doc_attribute_text(attr_node) {
+        return vec![doc];
+    }
+    if Some("lobster::trace") != attribute_path(attr_node).as_deref() {
+        return Vec::new();
+    }
+    let Some(token_tree) = attr_node
+        .get_child_kind(SyntaxKind::META)
+        .and_then(|meta| meta.get_child_kind(SyntaxKind::TOKEN_TREE))
+    else {
+        return Vec::new();
+    };
+    let text = token_tree.text().to_string();
+    let inner = text
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .unwrap_or(&text);
+    inner
+        .split(',')
+        .map(str::trim)
+        .filter(|reference| !reference.is_empty())
+        .map(|reference| format!("lobster-trace: {}", reference))
+        .collect()
+}
+
+/// Get the message of an error attribute.
+///
+/// Error enums deriving `thiserror::Error` describe each variant via `#[error("...")]`. Format
+/// arguments following the message are ignored, escape sequences of the message are resolved.
+///
+/// ### Parameters
+/// * `attr_node` - The attribute node to inspect.
+///
+/// ### Returns
+/// Some(String) with the message, None if the attribute is no error attribute with a message.
+pub(crate) fn error_message(attr_node: &SyntaxNode) -> Option<String> {
+    if Some("error") != attribute_path(attr_node).as_deref() {
+        return None;
+    }
+    attribute_string_value(attr_node)
+}
+
+/// Check if a struct or enum is marked as non-exhaustive.
+///
+/// ### Parameters
+/// * `item_node` - SyntaxNode of kind STRUCT or ENUM.
+///
+/// ### Returns
+/// True if the item has a `#[non_exhaustive]` attribute, false otherwise.
+pub(crate) fn is_non_exhaustive(item_node: &SyntaxNode) -> bool {
+    item_node
+        .get_children_kind(SyntaxKind::ATTR)
+        .iter()
+        .any(|attr| Some("non_exhaustive") == attribute_path(attr).as_deref())
+}
+
+/// Check if a file or module is only compiled for tests.
+///
+/// A file or local module is test-only if it holds an inner `#![cfg(test)]` attribute. The inner
+/// attributes of a file are children of the SOURCE_FILE node, the inner attributes of a local
+/// module are children of its ITEM_LIST node.
+///
+/// ### Parameters
+/// * `node` - SyntaxNode of kind SOURCE_FILE or MODULE.
+///
+/// ### Returns
+/// True if the node holds an inner `#![cfg(test)]` attribute, false otherwise.
+pub(crate) fn is_test_only(node: &SyntaxNode) -> bool {
+    let attr_parent = match node.kind() {
+        SyntaxKind::MODULE => match node.get_child_kind(SyntaxKind::ITEM_LIST) {
+            Some(item_list) => item_list,
+            None => return false,
+        },
+        _ => node.clone(),
+    };
+    attr_parent
+        .get_children_kind(SyntaxKind::ATTR)
+        .iter()
+        .filter(|attr| !attr.get_tokens_kind(SyntaxKind::BANG).is_empty())
+        .any(is_cfg_test)
+}
+
+/// Check if a module is gated by an outer `#[cfg(test)]` attribute.
+///
+/// Applies to module declarations and local modules alike, regardless of a visibility following
+/// the attribute.
+///
+/// ### Parameters
+/// * `mod_node` - SyntaxNode of kind MODULE.
+///
+/// ### Returns
+/// True if the module has an outer `#[cfg(test)]` attribute, false otherwise.
+pub(crate) fn is_cfg_test_gated(mod_node: &SyntaxNode) -> bool {
+    mod_node
+        .get_children_kind(SyntaxKind::ATTR)
+        .iter()
+        .filter(|attr| attr.get_tokens_kind(SyntaxKind::BANG).is_empty())
+        .any(is_cfg_test)
+}
+
+/// Check if an attribute is `cfg(test)`, either as inner or as outer attribute.
+///
+/// The tokens of the condition are compared without whitespace and comments, e.g. `cfg( test )`
+/// is `cfg(test)` as well.
+///
+/// ### Parameters
+/// * `attr` - SyntaxNode of kind ATTR.
+///
+/// ### Returns
+/// True if the attribute is `cfg(test)`, false otherwise.
+fn is_cfg_test(attr: &SyntaxNode) -> bool {
+    Some("cfg") == attribute_path(attr).as_deref()
+        && attr
+            .get_child_kind(SyntaxKind::META)
+            .and_then(|meta| meta.get_child_kind(SyntaxKind::TOKEN_TREE))
+            .is_some_and(|token_tree| {
+                let tokens: Vec<String> = token_tree
+                    .descendants_with_tokens()
+                    .filter_map(|element| element.into_token())
+                    .filter(|token| !token.kind().is_trivia())
+                    .map(|token| token.text().to_string())
+                    .collect();
+                tokens == ["(", "test", ")"]
+            })
+}
+
+/// Get the conditions of the `#[cfg(...)]` attributes of an item.
+///
+/// The condition is the text inside the parentheses of the attribute, e.g. `feature = "x"` for
+/// `#[cfg(feature = "x")]`. Inner attributes (`#![cfg(...)]`) are not considered.
+///
+/// ### Parameters
+/// * `node` - SyntaxNode of the item, e.g. of kind FN or IMPL.
+///
+/// ### Returns
+/// The conditions in the order of the attributes, empty if the item is not conditional.
+pub(crate) fn cfg_conditions(node: &SyntaxNode) -> Vec<String> {
+    node.get_children_kind(SyntaxKind::ATTR)
+        .iter()
+        .filter(|attr| attr.get_tokens_kind(SyntaxKind::BANG).is_empty())
+        .filter(|attr| Some("cfg") == attribute_path(attr).as_deref())
+        .filter_map(|attr| attr.get_child_kind(SyntaxKind::META))
+        .filter_map(|meta| meta.get_child_kind(SyntaxKind::TOKEN_TREE))
+        .map(|token_tree| {
+            let text = token_tree.text().to_string();
+            let inner = text
+                .strip_prefix('(')
+                .and_then(|t| t.strip_suffix(')'))
+                .unwrap_or(&text);
+            inner.trim().to_string()
+        })
+        .collect()
+}
+
+/// Attribute paths that mark test functions by default.
+pub(crate) const DEFAULT_TEST_ATTRIBUTES: [&str; 3] = ["test", "tokio::test", "async_std::test"];
+
+/// Check if a function is a test function.
+///
+/// A function is a test function if it has an attribute with one of the default test attribute
+/// paths (`#[test]`, `#[tokio::test]` or `#[async_std::test]`) or one of the additional paths,
+/// e.g. `rstest` for `#[rstest]`.
+///
+/// ### Parameters
+/// * `fn_node` - SyntaxNode of kind FN.
+/// * `test_attributes` - Additional attribute paths that mark test functions.
+///
+/// ### Returns
+/// True if the function is marked as test, false otherwise.
+pub(crate) fn is_test_function(fn_node: &SyntaxNode, test_attributes: &[String]) -> bool {
+    fn_node
+        .get_children_kind(SyntaxKind::ATTR)
+        .iter()
+        .filter_map(attribute_path)
+        .any(|path| {
+            DEFAULT_TEST_ATTRIBUTES.contains(&path.as_str()) || test_attributes.contains(&path)
+        })
+}
+
+/// Get the cases of a parameterized test function.
+///
+/// Every `#[test_case(...)]` attribute defines a case of the test. The case is named after its
+/// description, e.g. `small` for `#[test_case(1, 2 ; "small")]`, or after its 1-based position
+/// among the cases if it has no description.
+///
+/// ### Parameters
+/// * `fn_node` - SyntaxNode of kind FN.
+///
+/// ### Returns
+/// The names of the cases in the order of the attributes, empty if the function has none.
+pub(crate) fn test_cases(fn_node: &SyntaxNode) -> Vec<String> {
+    fn_node
+        .get_children_kind(SyntaxKind::ATTR)
+        .iter()
+        .filter(|attr| {
+            attribute_path(attr).is_some_and(|path| path.rsplit("::").next() == Some("test_case"))
+        })
+        .filter_map(|attr| attr.get_child_kind(SyntaxKind::META))
+        .filter_map(|meta| meta.get_child_kind(SyntaxKind::TOKEN_TREE))
+        .enumerate()
+        .map(|(index, token_tree)| {
+            test_case_description(&token_tree).unwrap_or_else(|| (index + 1).to_string())
+        })
+        .collect()
+}
+
+/// Get the description of a test case.
+///
+/// The description is the string literal following the arguments of the case, separated by a
+/// semicolon, e.g. `small` for `(1, 2 ; "small")`. Escape sequences are resolved.
+///
+/// ### Parameters
+/// * `token_tree` - SyntaxNode of kind TOKEN_TREE holding the arguments of the case.
+///
+/// ### Returns
+/// Some(String) with the description, None if the case has no description.
+fn test_case_description(token_tree: &SyntaxNode) -> Option<String> {
+    let description = token_tree
+        .children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .skip_while(|token| SyntaxKind::SEMICOLON != token.kind())
+        .find(|token| SyntaxKind::STRING == token.kind())?;
+    let description = ast::String::cast(description)?;
+    let value = description.value().ok()?;
+    Some(value.to_string())
+}
+
+/// Get the exported symbol name of a function.
+///
+/// Functions with a `#[no_mangle]` attribute are exported with their own name, functions with an
+/// `#[export_name = "symbol"]` attribute with the given symbol name. The attributes may be
+/// wrapped as unsafe attributes, e.g. `#[unsafe(no_mangle)]`.
+///
+/// ### Parameters
+/// * `fn_node` - SyntaxNode of kind FN.
+///
+/// ### Returns
+/// Some(String) with the exported symbol name, None if the function is not exported.
+pub(crate) fn extract_exported_symbol(fn_node: &SyntaxNode) -> Option<String> {
+    for attr_node in fn_node.get_children_kind(SyntaxKind::ATTR) {
+        match attribute_path(&attr_node).as_deref() {
+            Some("no_mangle") => {
+                let name_node = fn_node.get_child_kind(SyntaxKind::NAME)?;
+                return Some(name_node.text().to_string());
+            }
+            Some("export_name") => return attribute_string_value(&attr_node),
+            _ => (),
+        }
+    }
+    None
+}
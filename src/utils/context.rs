@@ -29,17 +29,20 @@
 
 //! Context module to efficiently track namespaces through module and file nestings.
 
-use std::{iter::Sum, ops};
+use std::{iter::Sum, ops, sync::Arc};
 
 /// Context struct to handle nested namespaces.
 ///
 /// The struct allows efficient creation, representation and most importantly combination of
 /// contexts. With contexts it's easy to represent nested modules and directories.
 /// This allows the easy creation of names and tags for traceable nodes.
+///
+/// The namespaces are shared reference counted strings, so cloning a Context does not allocate
+/// and combining Contexts only allocates the new stack, not the namespaces themselves.
 #[derive(Debug, Clone)]
-pub(crate) enum Context {
+pub enum Context {
     Empty,
-    Stacked(Vec<String>),
+    Stacked(Arc<[Arc<str>]>),
 }
 
 impl Context {
@@ -56,7 +59,7 @@ impl Context {
         if source.is_empty() {
             Context::Empty
         } else {
-            let stack = source.split('.').map(|s| s.to_string()).collect();
+            let stack = source.split('.').map(Arc::from).collect();
             Context::Stacked(stack)
         }
     }
@@ -67,13 +70,57 @@ impl Context {
     ///
     /// ### Returns
     /// String representation of the Context.
-    pub(crate) fn to_str(&self) -> String {
+    pub fn to_str(&self) -> String {
         match self {
             Context::Empty => "".to_string(),
             Context::Stacked(stack) => stack.join("."),
         }
     }
 
+    /// Get the namespaces of the Context.
+    ///
+    /// ### Returns
+    /// Vector of the namespaces, outermost first.
+    pub fn to_vec(&self) -> Vec<String> {
+        match self {
+            Context::Empty => Vec::new(),
+            Context::Stacked(stack) => stack.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Get the number of namespaces in the Context.
+    ///
+    /// ### Returns
+    /// Number of namespaces, 0 for an empty Context.
+    pub fn depth(&self) -> usize {
+        match self {
+            Context::Empty => 0,
+            Context::Stacked(stack) => stack.len(),
+        }
+    }
+
+    /// Truncate the Context from the left to the given depth.
+    ///
+    /// The innermost namespaces are kept, as they identify an item best.
+    ///
+    /// ### Parameters
+    /// * `max_depth` - Maximum number of namespaces to keep.
+    ///
+    /// ### Returns
+    /// New Context holding at most max_depth of the innermost namespaces.
+    pub fn truncate(&self, max_depth: usize) -> Self {
+        match self {
+            Context::Stacked(stack) if stack.len() > max_depth => {
+                if 0 == max_depth {
+                    Context::Empty
+                } else {
+                    Context::Stacked(stack[stack.len() - max_depth..].into())
+                }
+            }
+            _ => self.clone(),
+        }
+    }
+
     /// Combine with another context into a new Context
     ///
     /// This will create a new context with the other Context nested in this Context.
@@ -83,15 +130,13 @@ impl Context {
     ///
     /// ### Returns
     /// New Context that is a combination of both.
-    pub(crate) fn combine(&self, other: &Self) -> Self {
+    pub fn combine(&self, other: &Self) -> Self {
         match (self, other) {
             (Context::Empty, Context::Empty) => Context::Empty,
-            (Context::Stacked(s), Context::Empty) => Context::Stacked(s.clone()),
-            (Context::Empty, Context::Stacked(s)) => Context::Stacked(s.clone()),
+            (Context::Stacked(s), Context::Empty) => Context::Stacked(Arc::clone(s)),
+            (Context::Empty, Context::Stacked(s)) => Context::Stacked(Arc::clone(s)),
             (Context::Stacked(s1), Context::Stacked(s2)) => {
-                let mut new_stack = s1.clone();
-                new_stack.extend(s2.clone());
-                Context::Stacked(new_stack)
+                Context::Stacked(s1.iter().chain(s2.iter()).cloned().collect())
             }
         }
     }
@@ -131,6 +176,18 @@ impl ops::Add<String> for &Context {
 
 impl<'a> Sum<&'a Context> for Context {
     fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
-        iter.fold(Context::Empty, |acc, c| &acc + c)
+        // Collect all namespaces first to allocate the combined stack only once.
+        let stack: Vec<Arc<str>> = iter
+            .filter_map(|c| match c {
+                Context::Empty => None,
+                Context::Stacked(s) => Some(s.iter().cloned()),
+            })
+            .flatten()
+            .collect();
+        if stack.is_empty() {
+            Context::Empty
+        } else {
+            Context::Stacked(stack.into())
+        }
     }
 }
@@ -0,0 +1,147 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Utility functions to inspect macro calls.
+
+use ra_ap_syntax::{ast, AstNode, AstToken, Parse, SourceFile, SyntaxKind, SyntaxNode, TextSize};
+use std::path::PathBuf;
+
+use crate::syntax_extensions::Searchable;
+use crate::visitor::PARSER_EDITION;
+
+/// Check if a macro call is located at item position.
+///
+/// Macro calls at item position are direct children of a source file, of the item list of a
+/// local module or of the item list of an impl block or trait. Macro calls in expressions or
+/// statements are not at item position.
+///
+/// ### Parameters
+/// * `macro_call_node` - SyntaxNode of kind MACRO_CALL.
+///
+/// ### Returns
+/// true if the macro call is located at item position.
+pub(crate) fn is_item_position(macro_call_node: &SyntaxNode) -> bool {
+    macro_call_node
+        .parent()
+        .map(|parent| {
+            [
+                SyntaxKind::SOURCE_FILE,
+                SyntaxKind::ITEM_LIST,
+                SyntaxKind::ASSOC_ITEM_LIST,
+            ]
+            .contains(&parent.kind())
+        })
+        .unwrap_or(false)
+}
+
+/// Get the name of the called macro.
+///
+/// ### Parameters
+/// * `macro_call_node` - SyntaxNode of kind MACRO_CALL.
+///
+/// ### Returns
+/// Some(String) with the path of the called macro, e.g. `include` or `std::include`.
+pub(crate) fn macro_name(macro_call_node: &SyntaxNode) -> Option<String> {
+    let path_node = macro_call_node.get_child_kind(SyntaxKind::PATH)?;
+    Some(path_node.text().to_string())
+}
+
+/// Extracts the path from an `include!` macro call.
+///
+/// Checks if the macro call is an `include!` call with a string literal argument.
+/// If this is the case, the value of the literal is extracted as a path, e.g. `gen/items.rs` for
+/// `include!("gen/items.rs")` or `include!(r"gen/items.rs")`.
+///
+/// ### Parameters
+/// * `macro_call_node` - SyntaxNode of kind MACRO_CALL.
+///
+/// ### Returns
+/// Some(PathBuf) if the macro call includes a file given by a string literal, otherwise None.
+pub(crate) fn extract_include_path(macro_call_node: &SyntaxNode) -> Option<PathBuf> {
+    let name = macro_name(macro_call_node)?;
+    if !["include", "std::include", "core::include"].contains(&name.as_str()) {
+        return None;
+    }
+    let token_tree = macro_call_node.get_child_kind(SyntaxKind::TOKEN_TREE)?;
+    let string_token = token_tree
+        .get_tokens_kind(SyntaxKind::STRING)
+        .first()
+        .cloned()?;
+    // The value of the literal, so that raw strings and escape sequences are resolved.
+    let path_literal = ast::String::cast(string_token)?;
+    let path_string = path_literal.value().ok()?;
+    Some(PathBuf::from(path_string.as_ref()))
+}
+
+/// Body of a macro call, parsed as item list.
+pub(crate) struct MacroBody {
+    /// Offset of the body in the file of the macro call.
+    pub(crate) offset: TextSize,
+    /// Parse of the body, with offsets relative to the start of the body.
+    parse: Parse<SourceFile>,
+}
+
+impl MacroBody {
+    /// Get the items defined in the body.
+    ///
+    /// ### Returns
+    /// The functions and structs defined at the top level of the body.
+    pub(crate) fn items(&self) -> Vec<SyntaxNode> {
+        self.parse
+            .tree()
+            .syntax()
+            .children()
+            .filter(|item| [SyntaxKind::FN, SyntaxKind::STRUCT].contains(&item.kind()))
+            .collect()
+    }
+}
+
+/// Parse the body of a macro call as item list.
+///
+/// Macro bodies are token trees that are not parsed as items, even if they hold item definitions
+/// (e.g. `items! { fn generated() {} }`). The body is parsed on its own, so the offsets of the
+/// parsed nodes are relative to the start of the body. This is a best-effort parse, bodies that
+/// are not item lists yield no items.
+///
+/// ### Parameters
+/// * `macro_call_node` - SyntaxNode of kind MACRO_CALL.
+///
+/// ### Returns
+/// Some MacroBody if the body defines functions or structs, None otherwise.
+pub(crate) fn parse_macro_body(macro_call_node: &SyntaxNode) -> Option<MacroBody> {
+    let token_tree = macro_call_node.get_child_kind(SyntaxKind::TOKEN_TREE)?;
+    let text = token_tree.text().to_string();
+    // Strip the delimiters of the token tree.
+    let body = text.get(1..text.len().saturating_sub(1))?;
+    let macro_body = MacroBody {
+        offset: token_tree.text_range().start() + TextSize::from(1),
+        parse: SourceFile::parse(body, PARSER_EDITION),
+    };
+    (!macro_body.items().is_empty()).then_some(macro_body)
+}
@@ -31,6 +31,9 @@
 //!
 //! Collection of different utility functions.
 
-pub(crate) mod context;
+pub(crate) mod attributes;
+pub mod context;
 pub(crate) mod extract_path_attr;
+pub(crate) mod macro_calls;
 pub(crate) mod module_resolution;
+pub(crate) mod re_exports;
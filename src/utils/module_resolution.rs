@@ -29,8 +29,10 @@
 
 //! Utility functions to resolve module declarations to file paths.
 
+use crate::error::LobsterError;
+use crate::gitignore::Gitignore;
 use crate::utils::context::Context;
-use std::fs::{self, DirEntry};
+use std::fs;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 
@@ -45,12 +47,22 @@ use std::path::{Path, PathBuf};
 /// * `current_file` - Path to the current file (where the module was declared via the ```mod```
 ///   keyword).
 /// * `target_module_name` - Module name (The module name specified after the ```mod``` keyword).
+/// * `case_insensitive` - Resolve file and directory names case-insensitively if no exact match
+///   exists.
+/// * `is_crate_root` - The current file is a crate root, e.g. `src/bin/tool.rs` or `build.rs`.
+///   Crate roots resolve modules like main.rs, lib.rs and mod.rs, regardless of their name.
+/// * `gitignore` - Optional Gitignore, ignored files and directories are not considered.
+/// * `errors` - Problems found while resolving the module are appended to it.
 ///
 /// ### Returns
 /// Some(PathBuf, Context) if the module could be resolved to a path.
 pub(crate) fn resolve_module_declaration(
     current_file: &Path,
     target_module_name: &str,
+    case_insensitive: bool,
+    is_crate_root: bool,
+    gitignore: Option<&Gitignore>,
+    errors: &mut Vec<LobsterError>,
 ) -> Option<(PathBuf, Context)> {
     // Get cwd and target file name.
     let current_path = current_file.parent()?;
@@ -58,21 +70,23 @@ pub(crate) fn resolve_module_declaration(
     let file_target = target_module_name.to_string() + ".rs";
 
     // Read cwd contents.
-    let directory_read_results = fs::read_dir(current_path).ok()?;
-    let directory_content: Vec<PathBuf> = directory_read_results
-        .filter_map(|entry_result| entry_result.ok().map(|content| content.path()))
-        .collect();
+    let directory_content = read_directory(current_path, gitignore)?;
 
-    // For main.rs, lib.rs or mod.rs,
+    // For main.rs, lib.rs, mod.rs or other crate roots,
     // Rust tries to resolve the module in the current directory.
-    if ["main", "lib", "mod"].contains(&current_file_stem) {
+    if is_crate_root || ["main", "lib", "mod"].contains(&current_file_stem) {
         // Option 1: file named target.rs
-        if let Some(file_result) = check_file_module(&directory_content, &file_target) {
-            Some(file_result)
-        } else {
-            // Option 2: target directory with mod.rs.
-            check_directory_module(&directory_content, target_module_name)
-        }
+        // Option 2: target directory with mod.rs.
+        let file_module =
+            check_file_module(&directory_content, &file_target, case_insensitive, errors);
+        let directory_module = check_directory_module(
+            &directory_content,
+            target_module_name,
+            case_insensitive,
+            gitignore,
+            errors,
+        );
+        prefer_file_module(file_module, directory_module, errors)
     } else {
         // For files other than main.rs, lib.rs or mod.rs,
         // Rust tries to resolve the submodule in a subdirectory with the same name as the current
@@ -82,10 +96,75 @@ pub(crate) fn resolve_module_declaration(
             &file_target,
             target_module_name,
             current_file_stem,
+            case_insensitive,
+            gitignore,
+            errors,
         )
     }
 }
 
+/// Read the paths of the contents of a directory.
+///
+/// ### Parameters
+/// * `directory` - Path of the directory to read.
+/// * `gitignore` - Optional Gitignore, ignored contents are skipped.
+///
+/// ### Returns
+/// Some Vector of the paths of the contents, None if the directory could not be read.
+fn read_directory(directory: &Path, gitignore: Option<&Gitignore>) -> Option<Vec<PathBuf>> {
+    let directory_content = fs::read_dir(directory)
+        .ok()?
+        .filter_map(|entry_result| entry_result.ok().map(|content| content.path()))
+        .filter(|path| !gitignore.is_some_and(|gitignore| gitignore.is_ignored(path)))
+        .collect();
+    Some(directory_content)
+}
+
+/// Find a directory entry with the given name.
+///
+/// Searches the provided directory contents for an entry with the given file name.
+/// An exact match is always preferred. If no exact match exists and case-insensitive matching is
+/// enabled, an entry whose name only differs in casing is returned and reported, as this
+/// resolution would fail on case-sensitive file systems.
+///
+/// ### Parameters
+/// * `directory_content` - Paths to contents of the current directory.
+/// * `target_name` - File or directory name to search for.
+/// * `case_insensitive` - Allow matches that only differ in casing.
+/// * `is_dir` - Search for a directory instead of a file.
+/// * `errors` - A match that only differs in casing is appended to it.
+///
+/// ### Returns
+/// Some(&PathBuf) to the matching entry, if one is found.
+fn find_entry<'a>(
+    directory_content: &'a [PathBuf],
+    target_name: &str,
+    case_insensitive: bool,
+    is_dir: bool,
+    errors: &mut Vec<LobsterError>,
+) -> Option<&'a PathBuf> {
+    let candidates: Vec<(&PathBuf, &str)> = directory_content
+        .iter()
+        .filter(|entry| entry.is_dir() == is_dir)
+        .filter_map(|entry| Some((entry, entry.file_name()?.to_str()?)))
+        .collect();
+
+    if let Some((entry, _)) = candidates.iter().find(|(_, name)| target_name == *name) {
+        Some(entry)
+    } else if case_insensitive {
+        let (entry, _) = candidates
+            .iter()
+            .find(|(_, name)| target_name.eq_ignore_ascii_case(name))?;
+        errors.push(LobsterError::ModuleCasing {
+            module: target_name.to_string(),
+            resolved: entry.to_path_buf(),
+        });
+        Some(entry)
+    } else {
+        None
+    }
+}
+
 /// Check for a file in the given directory contents with the module name.
 ///
 /// Searches the provided directory contents for a rust source file that would match the module
@@ -95,22 +174,26 @@ pub(crate) fn resolve_module_declaration(
 /// ### Parameters
 /// * `directory_content` - Paths to contents of the current directory.
 /// * `file_target` - File name to search for to resolve the module.
+/// * `case_insensitive` - Allow file names that only differ in casing.
+/// * `errors` - Problems found while resolving the module are appended to it.
 ///
 /// ### Returns
 /// Some(PathBuf, Context) if the module could be resolved to a file.
 fn check_file_module(
     directory_content: &[PathBuf],
     file_target: &str,
+    case_insensitive: bool,
+    errors: &mut Vec<LobsterError>,
 ) -> Option<(PathBuf, Context)> {
-    for directory_entry in directory_content {
-        if let Some(file_name) = directory_entry.file_name() {
-            if file_target == file_name.to_str().unwrap() {
-                // Return path to target_name.rs
-                return Some((directory_entry.deref().to_path_buf(), Context::Empty));
-            }
-        }
-    }
-    None
+    // Return path to target_name.rs
+    find_entry(
+        directory_content,
+        file_target,
+        case_insensitive,
+        false,
+        errors,
+    )
+    .map(|file_path| (file_path.deref().to_path_buf(), Context::Empty))
 }
 
 /// Check for a directory in the given directory contents with the module name that contains a
@@ -124,30 +207,33 @@ fn check_file_module(
 /// ### Parameters
 /// * `directory_content` - Paths to contents of the current directory.
 /// * `target_module_name` - Directory name to search for to resolve the module.
+/// * `case_insensitive` - Allow directory names that only differ in casing.
+/// * `gitignore` - Optional Gitignore, an ignored mod.rs is not considered.
+/// * `errors` - Problems found while resolving the module are appended to it.
 ///
 /// ### Returns
 /// Some(PathBuf, Context) if the module could be resolved to a directory (with mod.rs).
 fn check_directory_module(
     directory_content: &[PathBuf],
     target_module_name: &str,
+    case_insensitive: bool,
+    gitignore: Option<&Gitignore>,
+    errors: &mut Vec<LobsterError>,
 ) -> Option<(PathBuf, Context)> {
-    for directory_entry in directory_content {
-        if (directory_entry.is_dir()) && (directory_entry.ends_with(target_module_name)) {
-            let subdirectory_content: Vec<DirEntry> = fs::read_dir(directory_entry)
-                .ok()?
-                .filter_map(|entry_result| entry_result.ok())
-                .collect();
-            // If a subdirectory with the target name exists, find mod.rs in it.
-            for subdirectory_entry in subdirectory_content {
-                if let Some(file_name) = subdirectory_entry.path().file_name() {
-                    if "mod.rs" == file_name.to_str().unwrap() {
-                        // Return path to mod.rs.
-                        return Some((
-                            subdirectory_entry.path(),
-                            Context::from_str(target_module_name),
-                        ));
-                    }
-                }
+    let directory_entry = find_entry(
+        directory_content,
+        target_module_name,
+        case_insensitive,
+        true,
+        errors,
+    )?;
+    let subdirectory_content = read_directory(directory_entry, gitignore)?;
+    // If a subdirectory with the target name exists, find mod.rs in it.
+    for subdirectory_entry in subdirectory_content {
+        if let Some(file_name) = subdirectory_entry.file_name() {
+            if Some("mod.rs") == file_name.to_str() {
+                // Return path to mod.rs.
+                return Some((subdirectory_entry, Context::from_str(target_module_name)));
             }
         }
     }
@@ -164,6 +250,10 @@ fn check_directory_module(
 /// * `directory_content` - Paths to contents of the current directory.
 /// * `file_target` - File name to search for to resolve the module.
 /// * `target_module_name` - Directory name to search for to resolve the module.
+/// * `current_file_stem` - File stem of the current file, naming the subdirectory to search.
+/// * `case_insensitive` - Allow file and directory names that only differ in casing.
+/// * `gitignore` - Optional Gitignore, ignored files and directories are not considered.
+/// * `errors` - Problems found while resolving the module are appended to it.
 ///
 /// ### Returns
 /// Some(PathBuf, Context) if the module could be resolved to a source file or directory (with
@@ -173,28 +263,61 @@ fn check_nested_submodule(
     file_target: &str,
     target_module_name: &str,
     current_file_stem: &str,
+    case_insensitive: bool,
+    gitignore: Option<&Gitignore>,
+    errors: &mut Vec<LobsterError>,
 ) -> Option<(PathBuf, Context)> {
     // Find subdirectory with the same name as the current file.
     if let Some(subdirectory) = directory_content.iter().find(|directory_entry| {
         directory_entry.is_dir() && directory_entry.ends_with(current_file_stem)
     }) {
         // Get the contents of the subdirectory.
-        let subdirectory_content: Vec<PathBuf> = fs::read_dir(subdirectory)
-            .ok()?
-            .filter_map(|entry_result| entry_result.ok().map(|content| content.path()))
-            .collect();
+        let subdirectory_content = read_directory(subdirectory, gitignore)?;
         let subdirectory_context = Context::from_str(current_file_stem);
 
         // Try to resolve the submodule to a file or directory in the subdirectory.
-        if let Some((file_module_path, nested_context)) =
-            check_file_module(&subdirectory_content, file_target)
-        {
-            return Some((file_module_path, subdirectory_context + nested_context));
-        } else if let Some((directory_module_path, nested_context)) =
-            check_directory_module(&subdirectory_content, target_module_name)
-        {
-            return Some((directory_module_path, subdirectory_context + nested_context));
-        }
+        let file_module =
+            check_file_module(&subdirectory_content, file_target, case_insensitive, errors);
+        let directory_module = check_directory_module(
+            &subdirectory_content,
+            target_module_name,
+            case_insensitive,
+            gitignore,
+            errors,
+        );
+        let (module_path, nested_context) =
+            prefer_file_module(file_module, directory_module, errors)?;
+        return Some((module_path, subdirectory_context + nested_context));
     }
     None
 }
+
+/// Choose between a file module and a directory module.
+///
+/// If both a file module (`target.rs`) and a directory module (`target/mod.rs`) exist, the module
+/// is ambiguous and rustc reports an error. The ambiguity is reported and the file module is
+/// chosen.
+///
+/// ### Parameters
+/// * `file_module` - Result of the file module resolution.
+/// * `directory_module` - Result of the directory module resolution.
+/// * `errors` - An ambiguous module is appended to it.
+///
+/// ### Returns
+/// The file module if it exists, the directory module otherwise.
+fn prefer_file_module(
+    file_module: Option<(PathBuf, Context)>,
+    directory_module: Option<(PathBuf, Context)>,
+    errors: &mut Vec<LobsterError>,
+) -> Option<(PathBuf, Context)> {
+    match (file_module, directory_module) {
+        (Some(file_module), Some((directory_module_path, _))) => {
+            errors.push(LobsterError::ModuleAmbiguous {
+                file_module: file_module.0.clone(),
+                directory_module: directory_module_path,
+            });
+            Some(file_module)
+        }
+        (file_module, directory_module) => file_module.or(directory_module),
+    }
+}
@@ -0,0 +1,131 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Utility functions to inspect re-exports (`pub use`).
+
+use ra_ap_syntax::{SyntaxKind, SyntaxNode};
+
+use crate::syntax_extensions::Searchable;
+
+/// Check if a use item is a public re-export.
+///
+/// Only plain `pub` re-exports are part of the public surface, restricted visibilities like
+/// `pub(crate)` are not.
+///
+/// ### Parameters
+/// * `use_node` - SyntaxNode of kind USE.
+///
+/// ### Returns
+/// true if the use item has `pub` visibility.
+pub(crate) fn is_public_use(use_node: &SyntaxNode) -> bool {
+    use_node
+        .get_child_kind(SyntaxKind::VISIBILITY)
+        .is_some_and(|visibility| visibility.text() == "pub")
+}
+
+/// Get the names a use item makes available.
+///
+/// Each name is the last segment of a used path, or the name given via `as`. Braced lists
+/// (`a::{B, C}`) yield one name per entry, `self` in a list yields the name of the list prefix.
+/// Glob imports (`a::*`) and underscore imports (`as _`) do not define names and are skipped.
+///
+/// ### Parameters
+/// * `use_node` - SyntaxNode of kind USE.
+///
+/// ### Returns
+/// Names in the order of the use item.
+pub(crate) fn use_names(use_node: &SyntaxNode) -> Vec<String> {
+    use_paths(use_node)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Get the names a use item makes available together with the used paths.
+///
+/// Like `use_names`, with the full path of each name, e.g. (`C`, `a::b::C`) for
+/// `use a::{b::C};` or (`B`, `a`) for `use a::{self as B};`.
+///
+/// ### Parameters
+/// * `use_node` - SyntaxNode of kind USE.
+///
+/// ### Returns
+/// Tuples of name and path in the order of the use item.
+pub(crate) fn use_paths(use_node: &SyntaxNode) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    if let Some(use_tree) = use_node.get_child_kind(SyntaxKind::USE_TREE) {
+        collect_use_tree_entries(&use_tree, None, &mut entries);
+    }
+    entries
+}
+
+/// Recursively collect the names and paths of a use tree.
+///
+/// ### Parameters
+/// * `use_tree` - SyntaxNode of kind USE_TREE.
+/// * `prefix` - Path prefix of an enclosing braced list, if any.
+/// * `entries` - Vector the names and paths are appended to.
+fn collect_use_tree_entries(
+    use_tree: &SyntaxNode,
+    prefix: Option<&str>,
+    entries: &mut Vec<(String, String)>,
+) {
+    let path_text = use_tree
+        .get_child_kind(SyntaxKind::PATH)
+        .map(|path| path.text().to_string().replace(char::is_whitespace, ""));
+    let full_path = match (prefix, path_text.as_deref()) {
+        (Some(prefix), Some("self")) => Some(prefix.to_string()),
+        (Some(prefix), Some(path)) => Some(format!("{}::{}", prefix, path)),
+        (Some(prefix), None) => Some(prefix.to_string()),
+        (None, Some("self")) => None,
+        (None, path) => path.map(str::to_string),
+    };
+    let last_segment = full_path
+        .as_deref()
+        .and_then(|path| path.rsplit("::").next())
+        .map(str::to_string);
+
+    if let Some(use_tree_list) = use_tree.get_child_kind(SyntaxKind::USE_TREE_LIST) {
+        for nested_tree in use_tree_list.get_children_kind(SyntaxKind::USE_TREE) {
+            collect_use_tree_entries(&nested_tree, full_path.as_deref(), entries);
+        }
+    } else if !use_tree.get_tokens_kind(SyntaxKind::STAR).is_empty() {
+        // Glob imports do not define names on their own.
+    } else if let (Some(full_path), Some(last_segment)) = (full_path.clone(), last_segment) {
+        let name = match use_tree.get_child_kind(SyntaxKind::RENAME) {
+            Some(rename) => match rename.get_child_kind(SyntaxKind::NAME) {
+                Some(name) => name.text().to_string(),
+                // Underscore imports do not define names.
+                None => return,
+            },
+            None => last_segment,
+        };
+        entries.push((name, full_path));
+    }
+}
@@ -0,0 +1,58 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Validation of the items emitted in the lobster common interchange format.
+//!
+//! The checks catch regressions of the parsing, e.g. of the location tracking, that would
+//! silently produce a less useful report.
+
+use json::JsonValue;
+
+/// Find the items that carry references but have no resolvable location.
+///
+/// Items located without line, e.g. because the keyword of the item was never visited, carry
+/// references that can not be navigated to in the report.
+///
+/// ### Parameters
+/// * `items` - Items in the lobster common interchange format.
+///
+/// ### Returns
+/// One warning per item with references and without line, naming the item.
+pub fn unlocated_item_warnings<'a>(items: impl IntoIterator<Item = &'a JsonValue>) -> Vec<String> {
+    items
+        .into_iter()
+        .filter(|item| !item["refs"].is_empty() && item["location"]["line"].is_null())
+        .map(|item| {
+            format!(
+                "WARNING: Item {} has references but no location in {}.",
+                item["name"], item["location"]["file"]
+            )
+        })
+        .collect()
+}
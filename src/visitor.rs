@@ -30,43 +30,157 @@
 //! # Visitor trait and RustVisitor to traverse the SyntaxTree.
 
 use ra_ap_edition::Edition;
-use ra_ap_syntax::{AstNode, NodeOrToken, SourceFile, SyntaxKind, SyntaxNode, SyntaxToken};
+use ra_ap_syntax::{
+    AstNode, NodeOrToken, SourceFile, SyntaxKind, SyntaxNode, SyntaxToken, TextRange, TextSize,
+};
 use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::thread;
 
 use crate::{
+    error::LobsterError,
+    gitignore::Gitignore,
     location::FileReference,
     syntax_extensions::{Searchable, Visitable},
-    traceable_node::{NodeKind, RustTraceableNode},
+    traceable_node::{FnComplexity, FnModifiers, NodeKind, RustTraceableNode},
+    utils::attributes::{
+        attribute_annotations, cfg_conditions, error_message, extract_exported_symbol,
+        is_cfg_test_gated, is_non_exhaustive, is_test_function, is_test_only, test_cases,
+    },
     utils::context::Context,
     utils::extract_path_attr::extract_path_attribute,
+    utils::macro_calls::{extract_include_path, is_item_position, parse_macro_body, MacroBody},
     utils::module_resolution::resolve_module_declaration,
+    utils::re_exports::{is_public_use, use_names, use_paths},
 };
 
+/// Characters of requirement references and justifications, e.g. `something.example` or
+/// `PROJ/123`.
+const REF_CHARS: &str = r"[[:alnum:]\./_-]";
+
+/// Regex of `lobster-trace` annotations, compiled once for all comments.
+static TRACE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"lobster-trace(\((?<kind>[[:alnum:]_-]+)\))?: (?<ref>{}+)",
+        REF_CHARS
+    ))
+    .unwrap()
+});
+
+/// Regex of `lobster-exclude` justifications, compiled once for all comments.
+static JUST_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r#"lobster-exclude(-(?<direction>up|down|global))?: (?<just>{}+)( "(?<reason>[^"]*)")?"#,
+        REF_CHARS
+    ))
+    .unwrap()
+});
+
+/// Regex of `lobster-nocheck` annotations, compiled once for all comments.
+static NOCHECK_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"lobster-nocheck\b").unwrap());
+
+/// Kinds of the nodes that can hold module declarations and `include!` calls, the only nodes
+/// visited if just the modules are resolved (see VisitorOptions::modules_only).
+const MODULE_STRUCTURE: [SyntaxKind; 4] = [
+    SyntaxKind::SOURCE_FILE,
+    SyntaxKind::MODULE,
+    SyntaxKind::ITEM_LIST,
+    SyntaxKind::MACRO_CALL,
+];
+
+/// Rust edition the source files are parsed with.
+pub const PARSER_EDITION: Edition = Edition::Edition2024;
+
+/// Stack size of the threads the source files are parsed on.
+///
+/// The parser recurses for every nesting level of an expression, so deeply nested generated code
+/// needs a larger stack than the default of spawned threads (2 MiB). Nesting that exceeds even
+/// this stack still overflows it (see doc/README.md).
+pub const PARSER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Version of the ra_ap_syntax parser lobster-rust is built against.
+///
+/// Requirements on 0.0.x versions only match that exact version, so this has to be kept equal to
+/// the ra_ap_syntax requirement in Cargo.toml (checked by the golden tests). It is pinned here
+/// rather than read from Cargo.lock, which is missing if lobster-rust is built as a dependency or
+/// from a packaged crate.
+pub const RA_AP_SYNTAX_VERSION: &str = "0.0.257";
+
 /// Visitor trait
 ///
 /// Implementation of the Visitor trait is needed to visit structs implementing the Visitable trait.
 /// The methods node_enter, node_exit and token_visit have to be defined and function like callbacks
 /// while traversing a Visitable struct. The travel function will visit the root node given and
 /// recusively traverse the tree defined by it.
-pub(crate) trait Visitor {
+pub trait Visitor {
     fn node_enter(&mut self, node: &SyntaxNode);
     fn node_exit(&mut self, node: &SyntaxNode);
     fn token_visit(&mut self, token: &SyntaxToken);
     fn travel(&mut self, root: &SyntaxNode);
 }
 
+/// Extension of the RustVisitor.
+///
+/// Extensions are registered via the VisitorOptions and invoked alongside the built-in handlers of
+/// every RustVisitor, including the visitors of module files, e.g. to extract additional
+/// annotations. All callbacks have an empty default implementation.
+/// The innermost RustTraceableNode on the node stack is passed on to the callbacks, so that
+/// extensions can annotate it. On node enter, the callbacks are invoked after the built-in handler,
+/// e.g. the node of an entered function is already on the stack. On node exit, they are invoked
+/// before the built-in handler, while the node is still on the stack. Skipped test-only code is
+/// not passed on to extensions.
+///
+/// Module files may be parsed in parallel, so extensions have to be thread-safe.
+pub trait VisitorExtension: fmt::Debug + Send + Sync {
+    /// Callback for node enter.
+    ///
+    /// ### Parameters
+    /// * `node` - Syntax node that is visited.
+    /// * `current` - Innermost RustTraceableNode on the stack, if any.
+    fn node_enter(&self, _node: &SyntaxNode, _current: Option<&mut RustTraceableNode>) {}
+
+    /// Callback for node exit.
+    ///
+    /// ### Parameters
+    /// * `node` - Syntax node that was visited.
+    /// * `current` - Innermost RustTraceableNode on the stack, if any.
+    fn node_exit(&self, _node: &SyntaxNode, _current: Option<&mut RustTraceableNode>) {}
+
+    /// Callback for token visit.
+    ///
+    /// ### Parameters
+    /// * `token` - Syntax token that is visited.
+    /// * `current` - Innermost RustTraceableNode on the stack, if any.
+    fn token_visit(&self, _token: &SyntaxToken, _current: Option<&mut RustTraceableNode>) {}
+}
+
 /// Visitor data to hold the visitors mutable state.
 ///
-/// The visitor data includes whitespace data to keep track of the whitespace token information
-/// already parsed. The node stack is used to track nested nodes in the tree and allows inferring
+/// The visitor data includes the line index of the parsed file to calculate locations of tokens.
+/// The node stack is used to track nested nodes in the tree and allows inferring
 /// context information about enclosing nodes, while new nested nodes and tokens are being parsed.
 /// The node stack is also used to build a tree of RustTraceableNodes that can be accessed after the
 /// visitor is finished parsing by accessing the root node from the stack.
 struct VisitorData {
-    whitespace_data: WhitespaceData,
+    line_index: LineIndex,
     node_stack: Vec<RustTraceableNode>,
+    /// Number of macro invocations parsed so far, by name.
+    macro_invocations: HashMap<String, usize>,
+    /// Paths of the names imported by the use items at the top level of the file, by name, e.g.
+    /// `crate::shapes::Area` for `Area`.
+    use_paths: HashMap<String, String>,
+    /// Kind and range of the node whose content is currently skipped, e.g. test-only code.
+    skipped_node: Option<(SyntaxKind, TextRange)>,
+    /// Parsed body of the macro call currently entered, whose items are visited on its exit.
+    macro_body: Option<MacroBody>,
+    /// Problems found while parsing the file.
+    errors: Vec<LobsterError>,
 }
 
 impl VisitorData {
@@ -76,36 +190,191 @@ impl VisitorData {
     fn get_root(&self) -> Option<&RustTraceableNode> {
         self.node_stack.first()
     }
+
+    /// Pop the last node from the stack if it is of the given kind.
+    ///
+    /// ### Parameters
+    /// * `kind` - Expected NodeKind of the last node.
+    ///
+    /// ### Returns
+    /// Some RustTraceableNode if the last node is of the given kind, None otherwise.
+    fn pop_kind(&mut self, kind: NodeKind) -> Option<RustTraceableNode> {
+        if kind == self.node_stack.last()?.kind {
+            self.node_stack.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Pop the last node from the stack and add it as a child to its enclosing node.
+    ///
+    /// The root node is never popped, the stack is left untouched if it only holds the root node.
+    fn close_node(&mut self) {
+        if self.node_stack.len() > 1 {
+            if let Some(closed_node) = self.node_stack.pop() {
+                if let Some(enclosing_node) = self.node_stack.last_mut() {
+                    enclosing_node.append_child(closed_node);
+                }
+            }
+        }
+    }
+}
+
+/// Line index to calculate lines and columns from byte offsets.
+///
+/// The SyntaxTree from ra_ap_syntax only tracks byte ranges in the file, disregarding line
+/// information. The line index holds the byte offsets of all line starts of a file. It is built
+/// once from the source text, before the SyntaxTree is traversed, so the locations do not depend
+/// on the order in which tokens are visited.
+///
+/// Trees parsed from a part of the file, like macro bodies, have offsets relative to the start of
+/// that part. The base offset maps them to offsets in the file.
+struct LineIndex {
+    line_starts: Vec<usize>,
+    /// Offset of the currently traversed tree in the file, added to all offsets.
+    base: TextSize,
+}
+
+impl LineIndex {
+    /// Build the line index of a source text.
+    ///
+    /// ### Parameters
+    /// * `text` - Source text to index.
+    ///
+    /// ### Returns
+    /// New LineIndex holding the start offsets of all lines of the text.
+    fn new(text: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(pos, _)| pos + 1))
+            .collect();
+        LineIndex {
+            line_starts,
+            base: TextSize::from(0),
+        }
+    }
+
+    /// Calculate the position for a given byte offset.
+    ///
+    /// Provides the line and the column for a given byte offset. Lines and columns are 1-based.
+    ///
+    /// ### Parameters
+    /// * `offset` - Byte offset in the currently traversed tree to calculate line and column for.
+    ///
+    /// ### Returns
+    /// Tuple of line and column for the given offset.
+    fn calculate_location(&self, offset: TextSize) -> (usize, usize) {
+        let offset = usize::from(self.base + offset);
+        // Number of lines starting at or before the offset, at least 1 as line 1 starts at 0.
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let col = offset - self.line_starts[line - 1] + 1;
+        (line, col)
+    }
 }
 
-/// Whitespace data to track whitespace token info.
+/// Options to configure the parsing of the RustVisitor.
 ///
-/// The whitespace data is used to keep track of the current line in the file and the char position
-/// of the last parsed linebreak in the file. It should be updated when visiting WHITESPACE kind
-/// tokens. The data can then be used to provide accurate locations of functions and structs in the
-/// source file. This is used because the SyntaxTree from ra_ap_syntax only tracks character ranges
-/// in the file, disregarding line information.
-struct WhitespaceData {
-    current_line: usize,
-    last_linebrk: usize,
+/// The options are passed on to all module visitors spawned by a RustVisitor.
+#[derive(Debug, Clone, Default)]
+pub struct VisitorOptions {
+    /// Resolve module declarations to files and directories case-insensitively.
+    pub case_insensitive_modules: bool,
+    /// Name of the top-level context, replacing the filename of the entry file in names and tags.
+    pub root_name: Option<String>,
+    /// Budget of threads to parse module files in parallel, shared by all module visitors.
+    pub threads: ThreadBudget,
+    /// Skip files and directories ignored by the Gitignore when resolving module declarations.
+    pub gitignore: Option<Arc<Gitignore>>,
+    /// Part of the items their location points to.
+    pub locate_at: LocateAt,
+    /// Do not resolve module declarations, e.g. if the files to parse are given explicitly.
+    pub skip_modules: bool,
+    /// Parse test code for activity traces, test-only files and modules are not skipped.
+    pub activity: bool,
+    /// Attribute paths marking test functions in addition to `test`, `tokio::test` and
+    /// `async_std::test`, e.g. `rstest`.
+    pub test_attributes: Vec<String>,
+    /// Prefix removed from the captured requirement references, e.g. `CompanyProj.`.
+    pub strip_prefix: Option<String>,
+    /// Canonical path of the project directory. Modules resolved outside of it via path attributes
+    /// are reported. Defaults to the project root of the directory of the crate root file (see
+    /// project_root).
+    pub project_root: Option<PathBuf>,
+    /// Parse the bodies of macro calls at item position as item lists and trace the functions and
+    /// structs defined in them. Best-effort, as macros are not expanded.
+    pub parse_macro_bodies: bool,
+    /// Name the items of every file after the file itself, e.g. `nested.item` instead of
+    /// `main.sub.nested.item`, independent of the module path of the file.
+    pub file_as_context: bool,
+    /// Trace enums like structs, with their annotated variants as members.
+    pub trace_enums: bool,
+    /// Skip module declarations and local modules gated by an outer `#[cfg(test)]` attribute,
+    /// like the modules holding an inner `#![cfg(test)]` attribute.
+    pub cfg_filter: bool,
+    /// Only resolve module declarations and `include!` calls without parsing the items of the
+    /// files, e.g. to list the files that would be parsed.
+    pub modules_only: bool,
+    /// Extensions invoked alongside the built-in handlers of the visitors.
+    pub extensions: Vec<Arc<dyn VisitorExtension>>,
 }
 
-impl WhitespaceData {
-    /// Calculate the position for a given SyntaxToken.
+/// Part of an item that its location points to.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum LocateAt {
+    /// The keyword of the item, e.g. `fn` or `struct`.
+    #[default]
+    Keyword,
+    /// The name of the item, so that jumping to the location lands on the identifier.
+    Name,
+}
+
+/// Budget of additional threads, shared between all clones.
+///
+/// The budget holds the number of threads that may be started in addition to the current thread.
+/// The default budget is empty, leading to serial parsing.
+#[derive(Debug, Clone, Default)]
+pub struct ThreadBudget {
+    /// Number of threads that are still available.
+    available: Arc<AtomicUsize>,
+}
+
+impl ThreadBudget {
+    /// Create a new thread budget.
+    ///
+    /// ### Parameters
+    /// * `threads` - Total number of threads to use, including the current thread.
     ///
-    /// Provides the line and the column for a given SyntaxToken.
-    /// Tis is only correct if all WHITESPACE tokens before the element containing line breaks were
-    /// already parsed to whitespace data!
+    /// ### Returns
+    /// ThreadBudget allowing threads - 1 additional threads.
+    pub fn new(threads: usize) -> Self {
+        ThreadBudget {
+            available: Arc::new(AtomicUsize::new(threads.saturating_sub(1))),
+        }
+    }
+
+    /// Take threads from the budget.
     ///
     /// ### Parameters
-    /// * `token` - SyntaxToken to calculate line and column for.
+    /// * `wanted` - Number of threads wanted.
     ///
     /// ### Returns
-    /// Tuple of line and column for the given element.
-    fn calculate_token_location(&self, token: &SyntaxToken) -> (usize, usize) {
-        let element_start = usize::from(token.text_range().start());
-        let col = element_start - self.last_linebrk;
-        (self.current_line, col)
+    /// Number of threads taken, at most the wanted number. Must be released after use.
+    fn acquire(&self, wanted: usize) -> usize {
+        let mut taken = 0;
+        let _ = self
+            .available
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |available| {
+                taken = available.min(wanted);
+                Some(available - taken)
+            });
+        taken
+    }
+
+    /// Return threads to the budget.
+    ///
+    /// ### Parameters
+    /// * `threads` - Number of threads to return.
+    fn release(&self, threads: usize) {
+        let _ = self.available.fetch_add(threads, Ordering::SeqCst);
     }
 }
 
@@ -116,16 +385,26 @@ impl WhitespaceData {
 /// trait. The node_enter, node_exit and token_visit funtions work as a mapping
 /// from the nodes and tokes SyntaxKind to specific RustVisitor methods that define the handling of
 /// each node and token kind.
-pub(crate) struct RustVisitor {
+pub struct RustVisitor {
     /// Filepath to the source file (.rs) the visitor is parsing.
     filepath: PathBuf,
     /// Context String that is prepended to any tags and names found in this file.
     default_context: Context,
+    /// Options to configure the parsing.
+    options: VisitorOptions,
+    /// Whether the visitor is parsing the entry file (main.rs or lib.rs) of the project.
+    is_entry: bool,
+    /// Whether the visitor is parsing a file included via `include!`. Its items are named in the
+    /// context of the macro call, without the filename.
+    is_include: bool,
     /// Visitor data to track the visitors internal state while traversing the SyntaxTree.
     vdata: VisitorData,
     /// Other visitors that are used to visit files that were included via module declarations in
     /// this visitors source file.
     module_visitors: Vec<RustVisitor>,
+    /// Annotated declaration (`mod name;`) of the module in this visitors source file. Emitted as
+    /// module item of the file.
+    declaration: Option<RustTraceableNode>,
 }
 
 impl RustVisitor {
@@ -137,28 +416,81 @@ impl RustVisitor {
     /// ### Parameters
     /// * `filepath` - Path to the file the visitor shall parse.
     /// * `context` - Default context for the visitor, will be prepended to parsed names and tags.
-    ///   */
+    /// * `options` - VisitorOptions to configure the parsing.
     ///
     /// ### Returns
     /// A Rustvisitor for the given file.
-    pub(crate) fn new(filepath: PathBuf, context: Context) -> Self {
+    pub fn new(filepath: PathBuf, context: Context, mut options: VisitorOptions) -> Self {
+        if options.project_root.is_none() {
+            options.project_root = filepath.parent().and_then(project_root);
+        }
         RustVisitor {
             filepath,
             default_context: context,
+            options,
+            is_entry: true,
+            is_include: false,
             vdata: VisitorData {
-                whitespace_data: WhitespaceData {
-                    current_line: 1,
-                    last_linebrk: 0,
-                },
+                line_index: LineIndex::new(""),
                 node_stack: Vec::new(),
+                macro_invocations: HashMap::new(),
+                use_paths: HashMap::new(),
+                skipped_node: None,
+                macro_body: None,
+                errors: Vec::new(),
             },
             module_visitors: Vec::new(),
+            declaration: None,
+        }
+    }
+
+    /// Constructs a new RustVisitor for a module file, i.e. a file that is not a crate root.
+    ///
+    /// ### Parameters
+    /// * `filepath` - Path to the file the visitor shall parse.
+    /// * `context` - Context of the module, e.g. `main.sub` for `src/sub/nested.rs`. The filename
+    ///   is appended to it.
+    /// * `options` - VisitorOptions to configure the parsing.
+    ///
+    /// ### Returns
+    /// A Rustvisitor for the given file.
+    pub fn new_module(filepath: PathBuf, context: Context, options: VisitorOptions) -> Self {
+        RustVisitor {
+            is_entry: false,
+            ..RustVisitor::new(filepath, context, options)
+        }
+    }
+
+    /// Constructs a new RustVisitor for a file included by this visitors source file.
+    ///
+    /// ### Parameters
+    /// * `filepath` - Path to the file the visitor shall parse.
+    /// * `context` - Default context for the visitor, will be prepended to parsed names and tags.
+    ///
+    /// ### Returns
+    /// A Rustvisitor for the given file.
+    fn new_submodule_visitor(&self, filepath: PathBuf, context: Context) -> Self {
+        RustVisitor::new_module(filepath, context, self.options.clone())
+    }
+
+    /// Constructs a new RustVisitor for a file included via `include!` in this visitors file.
+    ///
+    /// ### Parameters
+    /// * `filepath` - Path to the included file.
+    /// * `context` - Context of the macro call, used as is for the items of the included file.
+    ///
+    /// ### Returns
+    /// A Rustvisitor for the given file.
+    fn new_include_visitor(&self, filepath: PathBuf, context: Context) -> Self {
+        RustVisitor {
+            is_include: true,
+            ..self.new_submodule_visitor(filepath, context)
         }
     }
 
     /// Builds a Context from any enclosing nodes on the stack.
     ///
-    /// Traverses the stack to find context nodes that hold context data.
+    /// Traverses the stack to find context, trait and function nodes that hold context data.
     /// Combines the Contexts of the context data into one Context.
     ///
     /// ### Returns
@@ -169,7 +501,7 @@ impl RustVisitor {
             .vdata
             .node_stack
             .iter()
-            .filter(|n| NodeKind::Context == n.kind)
+            .filter(|n| [NodeKind::Context, NodeKind::Trait, NodeKind::Function].contains(&n.kind))
             .filter_map(|rtn| rtn.context_data.as_ref())
             .map(|context_data| &context_data.context)
             .collect();
@@ -190,43 +522,208 @@ impl RustVisitor {
     fn get_filename(&self) -> String {
         self.filepath
             .file_stem()
-            .unwrap()
-            .to_string_lossy()
-            .to_string()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Get the name of the file level context.
+    ///
+    /// The file level context is the filename of the file the visitor is parsing, or the root name
+    /// if one is configured and the visitor is parsing the entry file.
+    ///
+    /// ### Returns
+    /// String containing the name of the file level context.
+    fn get_file_context(&self) -> String {
+        match &self.options.root_name {
+            Some(root_name) if self.is_entry => root_name.clone(),
+            _ => self.get_filename(),
+        }
+    }
+
+    /// Get the context of the items at the top level of the file.
+    ///
+    /// The context combines the default context and the file level context, e.g. `main.sub` for
+    /// sub.rs. Files are their own context root if configured via the options, the context is then
+    /// only the file level context, e.g. `sub`. Included files share the context of the macro
+    /// call, which already holds the context of the including file.
+    ///
+    /// ### Returns
+    /// Context of the items at the top level of the file.
+    fn get_file_root_context(&self) -> Context {
+        if self.is_include {
+            self.default_context.clone()
+        } else if self.options.file_as_context {
+            &Context::Empty + self.get_file_context()
+        } else {
+            &self.default_context + self.get_file_context()
+        }
+    }
+
+    /// Get the default context for visitors of declared submodules.
+    ///
+    /// All names start with the file level context of the entry file. Visitors of files declared
+    /// in the entry file therefore get it as default context. Files declared in other files get
+    /// their directory based context from the module resolution instead.
+    ///
+    /// ### Returns
+    /// The default context for visitors of submodules declared in this visitors file.
+    fn get_submodule_context(&self) -> Context {
+        if self.is_entry {
+            &self.default_context + self.get_file_context()
+        } else {
+            self.default_context.clone()
+        }
+    }
+
+    /// Record a parse error at the given offset of the file.
+    ///
+    /// ### Parameters
+    /// * `offset` - Byte offset of the error in the source text.
+    /// * `message` - Description of the error.
+    fn report_parse_error(&mut self, offset: TextSize, message: String) {
+        let (line, column) = self.vdata.line_index.calculate_location(offset);
+        self.vdata.errors.push(LobsterError::ParseError {
+            path: self.filepath.clone(),
+            line,
+            column,
+            message,
+        });
+    }
+
+    /// Calculate the location of an item.
+    ///
+    /// ### Parameters
+    /// * `item_node` - SyntaxNode of the item.
+    /// * `keyword_start` - Byte offset of the keyword of the item.
+    ///
+    /// ### Returns
+    /// Tuple of line and column of the keyword, or of the name of the item if configured via the
+    /// options and the item has a name.
+    fn item_location(&self, item_node: &SyntaxNode, keyword_start: TextSize) -> (usize, usize) {
+        let offset = match self.options.locate_at {
+            LocateAt::Keyword => keyword_start,
+            LocateAt::Name => item_node
+                .get_child_kind(SyntaxKind::NAME)
+                .map_or(keyword_start, |name| name.text_range().start()),
+        };
+        self.vdata.line_index.calculate_location(offset)
     }
 
     /// Parse the corresponding file for the RustVisitor.
     ///
-    /// Reads the contents of the file pointed to by the filepath.
-    /// Parses the contents of the file into a SyntaxTree.
-    /// Traverses the tree by calling travel on the root node of the tree.
+    /// Reads the contents of the file pointed to by the filepath and strips a leading UTF-8 BOM.
+    /// Parses the contents of the file into a SyntaxTree. Syntax errors are recorded, the tree is
+    /// parsed nonetheless.
+    /// Traverses the tree by calling travel on the root node of the tree. Parsing and traversal run
+    /// on a thread with a stack of PARSER_STACK_SIZE, independent of the stack of the caller.
     /// Recursively also parses all included modules by calling .parse_file() of its
     /// module_visitors.
-    pub(crate) fn parse_file(&mut self) {
+    pub fn parse_file(&mut self) {
         match fs::read_to_string(&self.filepath) {
-            Err(e) => println!("WARNING: File: {:#?}\n{}", &self.filepath, e),
+            Err(e) => self.vdata.errors.push(LobsterError::FileRead {
+                path: self.filepath.clone(),
+                message: e.to_string(),
+            }),
             Ok(text) => {
-                let parse = SourceFile::parse(&text, Edition::Edition2024);
-                let tree: SourceFile = parse.tree();
-                let root_node = tree.syntax();
+                // A leading UTF-8 byte order mark would shift all offsets of the first line.
+                let text = text.strip_prefix('\u{feff}').unwrap_or(&text);
+                thread::scope(|scope| {
+                    thread::Builder::new()
+                        .stack_size(PARSER_STACK_SIZE)
+                        .spawn_scoped(scope, || self.parse_text(text))
+                        .expect("Failed to spawn the parser thread")
+                        .join()
+                        .unwrap_or_else(|e| panic::resume_unwind(e));
+                });
+                self.parse_module_files();
+            }
+        }
+    }
 
-                self.travel(root_node);
+    /// Parse the text of the file and traverse its SyntaxTree.
+    ///
+    /// ### Parameters
+    /// * `text` - Contents of the file, without byte order mark.
+    fn parse_text(&mut self, text: &str) {
+        self.vdata.line_index = LineIndex::new(text);
+        let parse = SourceFile::parse(text, PARSER_EDITION);
+        for error in parse.errors() {
+            self.report_parse_error(error.range().start(), error.to_string());
+        }
+        let tree: SourceFile = parse.tree();
+        let root_node = tree.syntax();
 
-                for subvisitor in self.module_visitors.iter_mut() {
-                    subvisitor.parse_file();
-                }
+        // Imports are collected up front, they are in scope of all items of the file.
+        self.vdata.use_paths = root_node
+            .get_children_kind(SyntaxKind::USE)
+            .iter()
+            .flat_map(use_paths)
+            .collect();
+        self.travel(root_node);
+    }
+
+    /// Parse the files of all module visitors.
+    ///
+    /// The module visitors are split into chunks that are parsed in parallel, as far as the thread
+    /// budget allows. Each chunk is parsed serially. Without thread budget, all module visitors are
+    /// parsed serially on the current thread. The order of the module visitors is kept either way.
+    fn parse_module_files(&mut self) {
+        let budget = self.options.threads.clone();
+        let extra_threads = budget.acquire(self.module_visitors.len().saturating_sub(1));
+
+        if 0 == extra_threads {
+            for subvisitor in self.module_visitors.iter_mut() {
+                subvisitor.parse_file();
             }
+        } else {
+            let chunk_size = self.module_visitors.len().div_ceil(extra_threads + 1);
+            thread::scope(|scope| {
+                let mut chunks = self.module_visitors.chunks_mut(chunk_size);
+                let own_chunk = chunks.next();
+                for chunk in chunks {
+                    scope.spawn(move || chunk.iter_mut().for_each(|v| v.parse_file()));
+                }
+                own_chunk.into_iter().flatten().for_each(|v| v.parse_file());
+            });
+            budget.release(extra_threads);
+        }
+    }
+
+    /// Returns references to its own root node and the root nodes of all module_visitors.
+    ///
+    /// Unlike get_traceable_nodes, the root nodes are kept in the visitors, so this can be called
+    /// any number of times, e.g. to inspect the nodes before converting them.
+    ///
+    /// ### Returns
+    /// Vector of references to the root nodes.
+    pub fn root_nodes(&self) -> Vec<&RustTraceableNode> {
+        let mut out_nodes: Vec<&RustTraceableNode> = self.vdata.get_root().into_iter().collect();
+        for subvisitor in self.module_visitors.iter() {
+            out_nodes.append(&mut subvisitor.root_nodes());
+        }
+        out_nodes
+    }
+
+    /// Returns the problems found while parsing its own file and the files of all module_visitors.
+    ///
+    /// ### Returns
+    /// Vector of references to the errors, in the order of the root nodes.
+    pub fn errors(&self) -> Vec<&LobsterError> {
+        let mut errors: Vec<&LobsterError> = self.vdata.errors.iter().collect();
+        for subvisitor in self.module_visitors.iter() {
+            errors.append(&mut subvisitor.errors());
         }
+        errors
     }
 
     /// Resturns its own root node and the root nodes of all module_visitors.
     ///
     /// Gathers its own root_node (first on the stack) and the root nodes of all module visitors in
-    /// a Vector.
+    /// a Vector. The root nodes are moved out of the visitors, so this can only be called once.
     ///
     /// ### Returns
     /// Vecor of the root nodes.
-    pub(crate) fn get_traceable_nodes(&mut self) -> Vec<RustTraceableNode> {
+    pub fn get_traceable_nodes(&mut self) -> Vec<RustTraceableNode> {
         let mut out_nodes: Vec<RustTraceableNode>;
         if self.vdata.node_stack.is_empty() {
             out_nodes = Vec::new();
@@ -245,14 +742,28 @@ impl RustVisitor {
 
     /// Callback for source node enter.
     ///
-    /// Puts the root node on the stack for the source file.
+    /// Puts the root node on the stack for the source file. The root node is located at the path
+    /// of the source file. If the module declaration of the file is annotated, a module item
+    /// located at the start of the file is added to the root node.
     ///
     /// ### Parameters
     /// * `source_node` - SyntaxNode of kind SOURCE. Top level node of a source file.
     fn enter_source(&mut self, source_node: &SyntaxNode) {
-        let mut root_node = RustTraceableNode::from_node(source_node, String::new()).unwrap();
-        root_node.name = self.get_filename();
-        self.vdata.node_stack.push(root_node);
+        let test_only = !self.options.activity && is_test_only(source_node);
+        if let Some(mut root_node) = RustTraceableNode::from_node(source_node, String::new()) {
+            root_node.name = self.get_filename();
+            root_node.location =
+                FileReference::new(self.filepath.display().to_string(), None, None);
+            if let Some(declaration) = self.declaration.take().filter(|_| !test_only) {
+                // The annotations of the declaration apply to the file as a whole.
+                root_node.append_child(self.to_file_module(declaration));
+            }
+            self.vdata.node_stack.push(root_node);
+        }
+        if test_only {
+            // The whole file is test code, only the empty root node is kept.
+            self.vdata.skipped_node = Some((source_node.kind(), source_node.text_range()));
+        }
     }
 
     /// Callback for FN node enter.
@@ -264,18 +775,32 @@ impl RustVisitor {
     /// ### Parameters
     /// * `fn_node` - SyntaxNode of kind FN.
     fn enter_fn(&mut self, fn_node: &SyntaxNode) {
-        // Set current location as approximation. Precise location will be set on fn keyword visit.
-        let (line, col) = (self.vdata.whitespace_data.current_line, 0);
-        let filepath = self.vdata.get_root().unwrap().name.clone();
+        // Set start of the node as approximation. Precise location will be set on fn keyword visit.
+        let (line, col) = self
+            .vdata
+            .line_index
+            .calculate_location(fn_node.text_range().start());
+        let filepath = self
+            .vdata
+            .get_root()
+            .map_or_else(|| self.get_filename(), |root| root.name.clone());
         let location = FileReference::new(filepath, Some(line), Some(col));
 
         // Check for enclosing context.
-        let context = &self.default_context + self.get_filename() + self.get_enclosing_context();
+        let context = self.get_file_root_context() + self.get_enclosing_context();
 
         // Parse node.
-        if let Some(node) =
+        if let Some(mut node) =
             RustTraceableNode::from_node_with_location(fn_node, location, context.to_str())
         {
+            node.symbol = extract_exported_symbol(fn_node);
+            node.modifiers = Some(FnModifiers::from_fn_node(fn_node));
+            node.complexity = Some(FnComplexity::from_fn_node(fn_node));
+            node.cfg = cfg_conditions(fn_node);
+            node.test_cases = test_cases(fn_node);
+            node.is_test = is_test_function(fn_node, &self.options.test_attributes)
+                || !node.test_cases.is_empty();
+            node.scope = context;
             self.vdata.node_stack.push(node);
         }
     }
@@ -283,42 +808,141 @@ impl RustVisitor {
     /// Callback for FN node exit.
     ///
     /// Retrieves the node from the stack and appends it as a child to the enclosing node.
+    /// Required trait methods (without a default body) are dropped, only default methods are kept.
+    /// The references of trait methods are recorded, so that methods of trait implementations can
+    /// inherit the references of the corresponding trait method once all files are parsed.
     ///
     /// ### Parameters
-    /// * `_` - SyntaxNode of kind FN.
-    fn exit_fn(&mut self, _: &SyntaxNode) {
-        if self.vdata.node_stack.last().unwrap().kind == NodeKind::Function {
-            // Pop function node from stack and add it to its parent node.
-            let closed_fn = self.vdata.node_stack.pop().unwrap();
+    /// * `fn_node` - SyntaxNode of kind FN.
+    fn exit_fn(&mut self, fn_node: &SyntaxNode) {
+        // Pop function node from stack and add it to its parent node.
+        if let Some(closed_fn) = self.vdata.pop_kind(NodeKind::Function) {
+            self.record_trait_method_refs(fn_node, &closed_fn);
+            let has_body = fn_node.get_child_kind(SyntaxKind::BLOCK_EXPR).is_some();
 
             if let Some(enclosing_node) = self.vdata.node_stack.last_mut() {
-                enclosing_node.append_child(closed_fn);
+                if has_body || NodeKind::Trait != enclosing_node.kind {
+                    enclosing_node.append_child(closed_fn);
+                }
             }
         }
     }
 
+    /// Record the references of trait methods.
+    ///
+    /// The references of the methods of a trait definition are recorded in the context data of
+    /// the trait node by method name. Methods of trait implementations inherit them after all
+    /// files are parsed, see `trait_refs::link_trait_method_refs`.
+    ///
+    /// ### Parameters
+    /// * `fn_node` - SyntaxNode of kind FN.
+    /// * `closed_fn` - RustTraceableNode of the function, not yet appended to the enclosing node.
+    fn record_trait_method_refs(&mut self, fn_node: &SyntaxNode, closed_fn: &RustTraceableNode) {
+        let Some(name_node) = fn_node.get_child_kind(SyntaxKind::NAME) else {
+            return;
+        };
+        let Some(enclosing_node) = self.vdata.node_stack.last_mut() else {
+            return;
+        };
+
+        if let (NodeKind::Trait, false, Some(context_data)) = (
+            enclosing_node.kind,
+            closed_fn.refs.is_empty(),
+            enclosing_node.context_data.as_mut(),
+        ) {
+            let _ = context_data
+                .method_refs
+                .insert(name_node.text().to_string(), closed_fn.refs.clone());
+        }
+    }
+
+    /// Resolve the path of an implemented trait to the name of the trait item.
+    ///
+    /// Paths starting with `crate`, `self` or `super` are resolved along the module tree. Paths
+    /// starting with a name imported at the top level of the file are resolved via the import.
+    /// Other paths are relative to the current module, e.g. `main.sub.Greet` for `Greet` in
+    /// sub.rs. Generic arguments are dropped.
+    ///
+    /// ### Parameters
+    /// * `trait_imp` - Path of the trait as written in the impl header, e.g. `shapes::Area<T>`.
+    ///
+    /// ### Returns
+    /// Name of the trait item, e.g. `main.shapes.Area`.
+    fn resolve_trait_path(&self, trait_imp: &str) -> String {
+        let path = trait_imp.split('<').next().unwrap_or(trait_imp).trim();
+        let mut segments: Vec<&str> = path
+            .trim_start_matches("::")
+            .split("::")
+            .map(str::trim)
+            .collect();
+        // Imported names are replaced by their path once, the path is resolved as written.
+        let imported_path;
+        if let Some(use_path) = self.vdata.use_paths.get(segments[0]) {
+            imported_path = use_path.clone();
+            segments.splice(0..1, imported_path.split("::").map(str::trim));
+        }
+
+        let file_root_context = self.get_file_root_context();
+        let mut resolved: Vec<String> = if "crate" == segments[0] {
+            let _ = segments.remove(0);
+            file_root_context.to_vec().into_iter().take(1).collect()
+        } else {
+            (file_root_context + self.get_enclosing_context()).to_vec()
+        };
+        for segment in segments {
+            match segment {
+                "self" => (),
+                "super" => {
+                    let _ = resolved.pop();
+                }
+                _ => resolved.push(segment.to_string()),
+            }
+        }
+        resolved.join(".")
+    }
+
     /// Callback for STRUCT node enter.
     ///
-    /// Parses struct information for the given STRUCT node.
-    /// Determines location, context, name and builds and puts the RustTraceableNode on the node
-    /// stack.
+    /// Parses struct information for the given STRUCT node (see enter_data_type).
     ///
     /// ### Parameters
     /// * `struct_node` - SyntaxNode of kind STRUCT.
     fn enter_struct(&mut self, struct_node: &SyntaxNode) {
-        // Set current location as approximation. Precise location will be set on struct keyword
-        // visit.
-        let (line, col) = (self.vdata.whitespace_data.current_line, 1);
-        let filepath = self.vdata.get_root().unwrap().name.clone();
+        self.enter_data_type(struct_node, SyntaxKind::STRUCT_KW);
+    }
+
+    /// Parse a struct or enum node and put it on the node stack.
+    ///
+    /// Determines location, context, name and builds and puts the RustTraceableNode on the node
+    /// stack. The node is located at its keyword, or at its name if configured.
+    ///
+    /// ### Parameters
+    /// * `type_node` - SyntaxNode of kind STRUCT or ENUM.
+    /// * `keyword_kind` - Kind of the keyword of the node, STRUCT_KW or ENUM_KW.
+    fn enter_data_type(&mut self, type_node: &SyntaxNode, keyword_kind: SyntaxKind) {
+        let keyword_start = type_node
+            .get_tokens_kind(keyword_kind)
+            .first()
+            .map_or(type_node.text_range().start(), |keyword| {
+                keyword.text_range().start()
+            });
+        let (line, col) = self.item_location(type_node, keyword_start);
+        let filepath = self
+            .vdata
+            .get_root()
+            .map_or_else(|| self.get_filename(), |root| root.name.clone());
         let location = FileReference::new(filepath, Some(line), Some(col));
 
         // Check for enclosing context.
-        let context = &self.default_context + self.get_filename() + self.get_enclosing_context();
+        let context = self.get_file_root_context() + self.get_enclosing_context();
 
         // Parse node.
-        if let Some(node) =
-            RustTraceableNode::from_node_with_location(struct_node, location, context.to_str())
+        if let Some(mut node) =
+            RustTraceableNode::from_node_with_location(type_node, location, context.to_str())
         {
+            node.cfg = cfg_conditions(type_node);
+            node.non_exhaustive = is_non_exhaustive(type_node);
+            node.scope = context;
             self.vdata.node_stack.push(node);
         }
     }
@@ -326,29 +950,206 @@ impl RustVisitor {
     /// Callback for STRUCT node exit.
     ///
     /// Retrieves the node from the stack and appends it as a child to the enclosing node.
+    /// Fields annotated via doc comments are added as children of the struct node.
     ///
     /// ### Parameters
-    /// * `_` - SyntaxNode of kind SRUCT.
-    fn exit_struct(&mut self, _: &SyntaxNode) {
-        if self.vdata.node_stack.last().unwrap().kind == NodeKind::Struct {
-            // Pop struct node from stack and add it to its parent node.
-            let closed_struct = self.vdata.node_stack.pop().unwrap();
-
+    /// * `struct_node` - SyntaxNode of kind SRUCT.
+    fn exit_struct(&mut self, struct_node: &SyntaxNode) {
+        // Pop struct node from stack and add it to its parent node.
+        if let Some(mut closed_struct) = self.vdata.pop_kind(NodeKind::Struct) {
+            for field in self.traced_fields(struct_node, &closed_struct) {
+                closed_struct.append_child(field);
+            }
             if let Some(enclosing_node) = self.vdata.node_stack.last_mut() {
                 enclosing_node.append_child(closed_struct);
             }
         }
     }
 
+    /// Parse the fields of a struct that are annotated with references or justifications.
+    ///
+    /// Fields are annotated via doc comments (`/// lobster-trace: ...`) directly above the field or
+    /// the equivalent doc attributes (`#[doc = "lobster-trace: ..."]`).
+    /// Fields without references and justifications are skipped.
+    ///
+    /// ### Parameters
+    /// * `struct_node` - SyntaxNode of kind STRUCT.
+    /// * `traceable_struct` - RustTraceableNode of the struct.
+    ///
+    /// ### Returns
+    /// Field RustTraceableNodes of the annotated fields.
+    fn traced_fields(
+        &self,
+        struct_node: &SyntaxNode,
+        traceable_struct: &RustTraceableNode,
+    ) -> Vec<RustTraceableNode> {
+        let Some(field_list) = struct_node.get_child_kind(SyntaxKind::RECORD_FIELD_LIST) else {
+            return Vec::new();
+        };
+        field_list
+            .get_children_kind(SyntaxKind::RECORD_FIELD)
+            .iter()
+            .filter_map(|field| {
+                let name_node = field.get_child_kind(SyntaxKind::NAME)?;
+                let (line, col) = self
+                    .vdata
+                    .line_index
+                    .calculate_location(name_node.text_range().start());
+                let location = FileReference::new(
+                    traceable_struct.location.filename.clone(),
+                    Some(line),
+                    Some(col),
+                );
+                let mut field_node = RustTraceableNode::new_field(
+                    traceable_struct,
+                    &name_node.text().to_string(),
+                    location,
+                );
+                self.annotate_member(field, &mut field_node);
+                field_node.is_traced().then_some(field_node)
+            })
+            .collect()
+    }
+
+    /// Callback for ENUM node enter.
+    ///
+    /// Parses enum information for the given ENUM node (see enter_data_type).
+    ///
+    /// ### Parameters
+    /// * `enum_node` - SyntaxNode of kind ENUM.
+    fn enter_enum(&mut self, enum_node: &SyntaxNode) {
+        self.enter_data_type(enum_node, SyntaxKind::ENUM_KW);
+    }
+
+    /// Callback for ENUM node exit.
+    ///
+    /// Retrieves the node from the stack and appends it as a child to the enclosing node.
+    /// Variants annotated via doc comments are added as children of the enum node.
+    ///
+    /// ### Parameters
+    /// * `enum_node` - SyntaxNode of kind ENUM.
+    fn exit_enum(&mut self, enum_node: &SyntaxNode) {
+        if let Some(mut closed_enum) = self.vdata.pop_kind(NodeKind::Enum) {
+            for variant in self.traced_variants(enum_node, &closed_enum) {
+                closed_enum.append_child(variant);
+            }
+            if let Some(enclosing_node) = self.vdata.node_stack.last_mut() {
+                enclosing_node.append_child(closed_enum);
+            }
+        }
+    }
+
+    /// Parse the variants of an enum that are annotated with references or justifications.
+    ///
+    /// Variants are annotated like fields, via doc comments or doc attributes. The message of an
+    /// `#[error("...")]` attribute, as used by thiserror, is recorded on the variant to relate
+    /// the requirement to the error it describes. Variants without references and justifications
+    /// are skipped.
+    ///
+    /// ### Parameters
+    /// * `enum_node` - SyntaxNode of kind ENUM.
+    /// * `traceable_enum` - RustTraceableNode of the enum.
+    ///
+    /// ### Returns
+    /// Variant RustTraceableNodes of the annotated variants.
+    fn traced_variants(
+        &self,
+        enum_node: &SyntaxNode,
+        traceable_enum: &RustTraceableNode,
+    ) -> Vec<RustTraceableNode> {
+        let Some(variant_list) = enum_node.get_child_kind(SyntaxKind::VARIANT_LIST) else {
+            return Vec::new();
+        };
+        variant_list
+            .get_children_kind(SyntaxKind::VARIANT)
+            .iter()
+            .filter_map(|variant| {
+                let name_node = variant.get_child_kind(SyntaxKind::NAME)?;
+                let (line, col) = self
+                    .vdata
+                    .line_index
+                    .calculate_location(name_node.text_range().start());
+                let location = FileReference::new(
+                    traceable_enum.location.filename.clone(),
+                    Some(line),
+                    Some(col),
+                );
+                let mut variant_node = RustTraceableNode::new_variant(
+                    traceable_enum,
+                    &name_node.text().to_string(),
+                    location,
+                );
+                self.annotate_member(variant, &mut variant_node);
+                if !variant_node.is_traced() {
+                    return None;
+                }
+                variant_node.message = variant
+                    .get_children_kind(SyntaxKind::ATTR)
+                    .iter()
+                    .find_map(error_message);
+                Some(variant_node)
+            })
+            .collect()
+    }
+
+    /// Add the annotations of the doc comments and attributes of a field or variant to its node.
+    ///
+    /// Doc comments and attributes are parsed together, so that mixed annotations keep their
+    /// source order. Like other items, members can hold references and justifications.
+    ///
+    /// ### Parameters
+    /// * `member_node` - SyntaxNode of kind RECORD_FIELD or VARIANT.
+    /// * `member` - Node of the field or variant to annotate.
+    fn annotate_member(&self, member_node: &SyntaxNode, member: &mut RustTraceableNode) {
+        let docs = member_node
+            .children_with_tokens()
+            .flat_map(|element| match element {
+                NodeOrToken::Token(token) if is_doc_comment(&token) => {
+                    vec![token.text().to_string()]
+                }
+                NodeOrToken::Node(node) if SyntaxKind::ATTR == node.kind() => {
+                    attribute_annotations(&node)
+                }
+                _ => Vec::new(),
+            });
+        for doc in docs {
+            annotate_node(member, &doc, self.options.strip_prefix.as_deref());
+        }
+    }
+
     /// Callback forIMPL node enter.
     ///
     /// Parses context information for the given IMPL node.
-    /// Puts the CONTEXT RustTraceableNode on the node stack.
+    /// Puts the CONTEXT RustTraceableNode on the node stack. Malformed impl nodes are put on the
+    /// stack as context without context data, to keep the stack consistent with node exits, and
+    /// are recorded as error. The `#[cfg(...)]` conditions of the impl block are recorded on the
+    /// context node.
     ///
     /// ### Parameters
     /// * `impl_node` - SyntaxNode of kind IMPL.
     fn enter_impl(&mut self, impl_node: &SyntaxNode) {
-        let node = RustTraceableNode::from_node(impl_node, String::new()).unwrap();
+        let mut node =
+            RustTraceableNode::from_node(impl_node, String::new()).unwrap_or_else(|| {
+                let (line, column) = self
+                    .vdata
+                    .line_index
+                    .calculate_location(impl_node.text_range().start());
+                self.vdata.errors.push(LobsterError::MalformedImpl {
+                    path: self.filepath.clone(),
+                    line,
+                    column,
+                });
+                RustTraceableNode::new_empty_context()
+            });
+        // Trait implementations inherit the references of the trait methods after parsing.
+        if let Some(context_data) = node.context_data.as_mut() {
+            context_data.trait_path = context_data
+                .trait_imp
+                .as_deref()
+                .map(|trait_imp| self.resolve_trait_path(trait_imp));
+        }
+        // The conditions of the impl block apply to all of its methods.
+        node.cfg = cfg_conditions(impl_node);
         self.vdata.node_stack.push(node);
     }
 
@@ -359,216 +1160,659 @@ impl RustVisitor {
     /// ### Parameters
     /// * `_` - SyntaxNode of kind IMPL.
     fn exit_impl(&mut self, _impl_node: &SyntaxNode) {
-        let closed_impl = self.vdata.node_stack.pop().unwrap();
-        if let Some(enclosing_node) = self.vdata.node_stack.last_mut() {
-            enclosing_node.append_child(closed_impl);
-        }
+        self.vdata.close_node();
     }
 
     /// Callback for MODULE node enter.
     ///
     /// Parses information for the given MODULE node.
-    /// Determines if the node represents a module declaration.
-    /// If so, the module is resolved to a file path and a module visitor for the new source file is
-    /// created. If the node instead represents a local module definition, it is parsed to a
-    /// context node and put on the stack.
+    /// Determines if the node represents a module declaration or a local module definition.
+    /// Both are put on the stack as context node, so that annotations of the module are collected.
+    /// Module declarations are resolved on exit. Local modules are located at the `mod` keyword
+    /// and named like other items.
+    /// Modules without a name are recorded as parse error. For local modules, a context without
+    /// context data is put on the stack instead, so that the enclosed items are still traced.
     ///
     /// ### Parameters
     /// * `mod_node` - SyntaxNode of kind MODULE.
     fn enter_module(&mut self, mod_node: &SyntaxNode) {
-        let name_node = mod_node.get_child_kind(SyntaxKind::NAME).unwrap();
-        let last_child = mod_node.children_with_tokens().last().unwrap();
-        match last_child {
-            NodeOrToken::Token(t) => {
-                if t.kind() == SyntaxKind::SEMICOLON {
-                    // Found module declaration. Resolve to corresponding file.
-                    let attrs = mod_node.get_children_kind(SyntaxKind::ATTR);
-                    let path_attributes: Vec<PathBuf> =
-                        attrs.iter().filter_map(extract_path_attribute).collect();
-
-                    if let Some(module_path) = path_attributes.first() {
-                        // Resolve the path given by the path attribute.
-                        self.module_visitors.push(RustVisitor::new(
-                            self.filepath.parent().unwrap().join(module_path),
-                            Context::Empty, /* This is not correct, need to resolve a Context
-                                             * from the path. */
-                        ));
-                    } else {
-                        // Follow the standard module declaration resolution.
-                        if let Some((modpath, context)) = resolve_module_declaration(
-                            &self.filepath,
-                            &name_node.text().to_string(),
-                        ) {
-                            let nested_context = &self.default_context + context;
-                            self.module_visitors
-                                .push(RustVisitor::new(modpath, nested_context));
-                        }
-                    }
-                }
+        let cfg_test_gated = self.options.cfg_filter && is_cfg_test_gated(mod_node);
+        if !self.options.activity && (is_test_only(mod_node) || cfg_test_gated) {
+            // Module with test code only, skip it including its submodules.
+            self.vdata.skipped_node = Some((mod_node.kind(), mod_node.text_range()));
+            return;
+        }
+        if mod_node.get_child_kind(SyntaxKind::NAME).is_none() {
+            self.report_parse_error(
+                mod_node.text_range().start(),
+                "Module without name".to_string(),
+            );
+        }
+        match mod_node.children_with_tokens().last() {
+            Some(NodeOrToken::Token(t)) if SyntaxKind::SEMICOLON == t.kind() => {
+                // Found module declaration. Collect its annotations until it is resolved on exit.
+                self.vdata
+                    .node_stack
+                    .push(RustTraceableNode::new_empty_context());
             }
-            NodeOrToken::Node(n) => {
-                if n.kind() == SyntaxKind::ITEM_LIST {
-                    // Found local module. Parse as Context.
-                    let context_node =
-                        RustTraceableNode::from_node(mod_node, String::new()).unwrap();
-                    self.vdata.node_stack.push(context_node);
-                }
+            Some(NodeOrToken::Node(n)) if SyntaxKind::ITEM_LIST == n.kind() => {
+                // Found local module. Parse as Context.
+                let context = self.get_file_root_context() + self.get_enclosing_context();
+                let context_node = match RustTraceableNode::from_node(mod_node, String::new()) {
+                    Some(mut node) => {
+                        node.name = format!("{}.{}", context.to_str(), node.name);
+                        let keyword_start = mod_node
+                            .get_tokens_kind(SyntaxKind::MOD_KW)
+                            .first()
+                            .map_or(mod_node.text_range().start(), |kw| kw.text_range().start());
+                        let (line, col) = self.item_location(mod_node, keyword_start);
+                        let filepath = self
+                            .vdata
+                            .get_root()
+                            .map_or_else(|| self.get_filename(), |root| root.name.clone());
+                        node.location = FileReference::new(filepath, Some(line), Some(col));
+                        node.scope = context;
+                        node
+                    }
+                    None => RustTraceableNode::new_empty_context(),
+                };
+                self.vdata.node_stack.push(context_node);
             }
+            _ => (),
         }
     }
 
     /// Callback for MODULE node exit.
     ///
-    /// Retrieves the node from the stack and appends it as a child to the enclosing node.
-    /// As only MODULE nodes representing a local module definitions are put on the node stack,
-    /// it is first checked if this applies to the node. Otherwise nothing is done.
+    /// Retrieves the context node of the module from the stack.
+    /// Module declarations are resolved to a file path and a module visitor for the new source
+    /// file is created. Annotations of the declaration are passed on to the module visitor.
+    /// Declarations that can not be resolved are recorded as error. With the skip_modules option,
+    /// declarations are not resolved at all.
+    /// Local modules are appended as a child to the enclosing node. Annotated local modules are
+    /// turned into module nodes, so that they are emitted as items.
     ///
     /// ### Parameters
     /// * `mod_node` - SyntaxNode of kind MODULE.
     fn exit_module(&mut self, mod_node: &SyntaxNode) {
-        // Only pop the last Context if this mod_node was added as a Context.
-        if let NodeOrToken::Node(n) = mod_node.children_with_tokens().last().unwrap() {
-            if n.kind() == SyntaxKind::ITEM_LIST {
-                let closed_context = self.vdata.node_stack.pop().unwrap();
-                if let Some(enclosing_node) = self.vdata.node_stack.last_mut() {
-                    enclosing_node.append_child(closed_context);
+        match mod_node.children_with_tokens().last() {
+            Some(NodeOrToken::Token(t)) if SyntaxKind::SEMICOLON == t.kind() => {
+                let declaration = self
+                    .vdata
+                    .pop_kind(NodeKind::Context)
+                    .filter(RustTraceableNode::is_traced);
+                if let Some(name_node) = mod_node
+                    .get_child_kind(SyntaxKind::NAME)
+                    .filter(|_| !self.options.skip_modules)
+                {
+                    self.resolve_module(mod_node, &name_node, declaration);
                 }
             }
+            Some(NodeOrToken::Node(n)) if SyntaxKind::ITEM_LIST == n.kind() => {
+                if let Some(mut closed_module) = self.vdata.pop_kind(NodeKind::Context) {
+                    if closed_module.context_data.is_some() && closed_module.is_traced() {
+                        closed_module.kind = NodeKind::Module;
+                    }
+                    if let Some(enclosing_node) = self.vdata.node_stack.last_mut() {
+                        enclosing_node.append_child(closed_module);
+                    }
+                }
+            }
+            _ => (),
         }
     }
 
+    /// Resolve a module declaration and create a module visitor for the resolved file.
+    ///
+    /// ### Parameters
+    /// * `mod_node` - SyntaxNode of kind MODULE, declaring the module.
+    /// * `name_node` - SyntaxNode of kind NAME, the name of the module.
+    /// * `declaration` - Optional annotated node of the declaration, passed on to the module
+    ///   visitor.
+    fn resolve_module(
+        &mut self,
+        mod_node: &SyntaxNode,
+        name_node: &SyntaxNode,
+        declaration: Option<RustTraceableNode>,
+    ) {
+        let attrs = mod_node.get_children_kind(SyntaxKind::ATTR);
+        let path_attributes: Vec<PathBuf> =
+            attrs.iter().filter_map(extract_path_attribute).collect();
+
+        let current_directory = self.filepath.parent().unwrap_or(Path::new(""));
+        let mut module_visitor = if let Some(module_path) = path_attributes.first() {
+            // Resolve the path given by the path attribute, including `..` components.
+            let Ok(modpath) = fs::canonicalize(current_directory.join(module_path)) else {
+                self.vdata.errors.push(LobsterError::ModuleUnresolved {
+                    path: self.filepath.clone(),
+                    module: name_node.text().to_string(),
+                });
+                return;
+            };
+            // Modules outside of the project are still parsed, but likely not intended.
+            if let Some(project_root) = &self.options.project_root {
+                if !modpath.starts_with(project_root) {
+                    self.vdata.errors.push(LobsterError::ModuleOutsideRoot {
+                        path: self.filepath.clone(),
+                        module: name_node.text().to_string(),
+                        resolved: modpath.clone(),
+                        root: project_root.clone(),
+                    });
+                }
+            }
+            self.new_submodule_visitor(
+                modpath,
+                self.get_submodule_context(), /* This is not complete, need to
+                                               * resolve a Context from the path. */
+            )
+        } else {
+            // Follow the standard module declaration resolution.
+            match resolve_module_declaration(
+                &self.filepath,
+                &name_node.text().to_string(),
+                self.options.case_insensitive_modules,
+                self.is_entry,
+                self.options.gitignore.as_deref(),
+                &mut self.vdata.errors,
+            ) {
+                Some((modpath, context)) => {
+                    let nested_context = &self.get_submodule_context() + context;
+                    self.new_submodule_visitor(modpath, nested_context)
+                }
+                None => {
+                    self.vdata.errors.push(LobsterError::ModuleUnresolved {
+                        path: self.filepath.clone(),
+                        module: name_node.text().to_string(),
+                    });
+                    return;
+                }
+            }
+        };
+        module_visitor.declaration = declaration;
+        self.module_visitors.push(module_visitor);
+    }
+
     /// Callback for TRAIT node enter.
     ///
     /// Put a trait node on the stack so that encompassed nodes can check their context.
+    /// The trait node holds the trait name as context, so default methods are named
+    /// `Trait.method`. The trait node itself is named and located like other items, so it can be
+    /// emitted as requirement.
     ///
     /// ### Parameters
     /// * `trait_node` - SyntaxNode of kind Trait.
     fn enter_trait(&mut self, trait_node: &SyntaxNode) {
-        // The node information is not needed for the output, only for context while parsing.
-        let traceable_trait_node = RustTraceableNode::from_node(trait_node, String::new()).unwrap();
+        let context = self.get_file_root_context() + self.get_enclosing_context();
+        let traceable_trait_node = match RustTraceableNode::from_node(trait_node, String::new()) {
+            Some(mut node) => {
+                node.name = format!("{}.{}", context.to_str(), node.name);
+                let keyword_start = trait_node
+                    .get_tokens_kind(SyntaxKind::TRAIT_KW)
+                    .first()
+                    .map_or(trait_node.text_range().start(), |kw| {
+                        kw.text_range().start()
+                    });
+                let (line, col) = self.item_location(trait_node, keyword_start);
+                let filepath = self
+                    .vdata
+                    .get_root()
+                    .map_or_else(|| self.get_filename(), |root| root.name.clone());
+                node.location = FileReference::new(filepath, Some(line), Some(col));
+                node.scope = context;
+                node
+            }
+            None => RustTraceableNode::new_empty_context(),
+        };
         self.vdata.node_stack.push(traceable_trait_node);
     }
 
     /// Callback for TRAIT node exit.
     ///
-    /// Remove the trait node from the stack.
+    /// Retrieves the node from the stack and appends it as a child to the enclosing node.
     ///
     /// ### Parameters
     /// * `_` - SyntaxNode of kind Trait.
     fn exit_trait(&mut self, _: &SyntaxNode) {
-        // No need to append the trait as a child, currently traits are unused in the output.
-        // This also filters out all functions defined in the trait.
-        let _ = self.vdata.node_stack.pop();
+        self.vdata.close_node();
+    }
+
+    /// Callback for MACRO_DEF node enter.
+    ///
+    /// Parses a declarative macro 2.0 definition (`macro name() {}`) like a struct. The macro node
+    /// is located at the `macro` keyword, so annotations in the macro body belong to the macro.
+    ///
+    /// ### Parameters
+    /// * `macro_def_node` - SyntaxNode of kind MACRO_DEF.
+    fn enter_macro_def(&mut self, macro_def_node: &SyntaxNode) {
+        let keyword_start = macro_def_node
+            .get_tokens_kind(SyntaxKind::MACRO_KW)
+            .first()
+            .map_or(macro_def_node.text_range().start(), |kw| {
+                kw.text_range().start()
+            });
+        let (line, col) = self.item_location(macro_def_node, keyword_start);
+        let filepath = self
+            .vdata
+            .get_root()
+            .map_or_else(|| self.get_filename(), |root| root.name.clone());
+        let location = FileReference::new(filepath, Some(line), Some(col));
+
+        // Check for enclosing context.
+        let context = self.get_file_root_context() + self.get_enclosing_context();
+
+        // Parse node.
+        if let Some(mut node) =
+            RustTraceableNode::from_node_with_location(macro_def_node, location, context.to_str())
+        {
+            node.scope = context;
+            self.vdata.node_stack.push(node);
+        }
+    }
+
+    /// Callback for MACRO_DEF node exit.
+    ///
+    /// Retrieves the node from the stack and appends it as a child to the enclosing node.
+    ///
+    /// ### Parameters
+    /// * `_` - SyntaxNode of kind MACRO_DEF.
+    fn exit_macro_def(&mut self, _: &SyntaxNode) {
+        if let Some(closed_macro) = self.vdata.pop_kind(NodeKind::Macro) {
+            if let Some(enclosing_node) = self.vdata.node_stack.last_mut() {
+                enclosing_node.append_child(closed_macro);
+            }
+        }
+    }
+
+    /// Callback for USE node enter.
+    ///
+    /// Public re-exports (`pub use`) are put on the stack as context node, so that annotations of
+    /// the re-export are collected until the re-exported names are emitted on exit. Other use
+    /// items are ignored.
+    ///
+    /// ### Parameters
+    /// * `use_node` - SyntaxNode of kind USE.
+    fn enter_use(&mut self, use_node: &SyntaxNode) {
+        if is_public_use(use_node) {
+            self.vdata
+                .node_stack
+                .push(RustTraceableNode::new_empty_context());
+        }
+    }
+
+    /// Callback for USE node exit.
+    ///
+    /// Appends a re-export node for every name made available by a public re-export to the
+    /// enclosing node, e.g. `main.B` and `main.C` for `pub use a::{B, C};`. The nodes carry the
+    /// annotations of the re-export and are located at the `use` keyword.
+    ///
+    /// ### Parameters
+    /// * `use_node` - SyntaxNode of kind USE.
+    fn exit_use(&mut self, use_node: &SyntaxNode) {
+        if !is_public_use(use_node) {
+            return;
+        }
+        let Some(declaration) = self.vdata.pop_kind(NodeKind::Context) else {
+            return;
+        };
+        let keyword_start = use_node
+            .get_tokens_kind(SyntaxKind::USE_KW)
+            .first()
+            .map_or(use_node.text_range().start(), |kw| kw.text_range().start());
+        let (line, col) = self.vdata.line_index.calculate_location(keyword_start);
+        let filepath = self
+            .vdata
+            .get_root()
+            .map_or_else(|| self.get_filename(), |root| root.name.clone());
+        let context = self.get_file_root_context() + self.get_enclosing_context();
+
+        for name in use_names(use_node) {
+            let mut re_export = declaration.clone();
+            re_export.kind = NodeKind::ReExport;
+            re_export.name = format!("{}.{}", context.to_str(), name);
+            re_export.location = FileReference::new(filepath.clone(), Some(line), Some(col));
+            re_export.scope = context.clone();
+            if let Some(enclosing_node) = self.vdata.node_stack.last_mut() {
+                enclosing_node.append_child(re_export);
+            }
+        }
+    }
+
+    /// Callback for MACRO_CALL node enter.
+    ///
+    /// Checks if the macro call is an `include!` of a source file at item position.
+    /// If so, a visitor for the included file is created. Items of the included file are named in
+    /// the context of the macro call, as if they were written in place of it.
+    /// Other macro calls at item position may generate untraced items. They are put on the stack
+    /// as placeholder nodes named after the macro. Repeated invocations of the same macro in the
+    /// same context are numbered, e.g. `main.make_fn_2`.
+    /// If macro bodies are parsed and the body holds items, the body is skipped here, so that its
+    /// annotations belong to its items instead of the placeholder. The items are visited on exit.
+    ///
+    /// ### Parameters
+    /// * `macro_call_node` - SyntaxNode of kind MACRO_CALL.
+    fn enter_macro_call(&mut self, macro_call_node: &SyntaxNode) {
+        if !is_item_position(macro_call_node) {
+            return;
+        }
+
+        let context = self.get_file_root_context() + self.get_enclosing_context();
+        if let Some(include_path) = extract_include_path(macro_call_node) {
+            // The included path is relative to the current file.
+            if let Some(current_directory) = self.filepath.parent() {
+                let include_visitor =
+                    self.new_include_visitor(current_directory.join(include_path), context);
+                self.module_visitors.push(include_visitor);
+            }
+        } else {
+            // Set start of the node as approximation. Precise location will be set on bang visit.
+            let (line, col) = self
+                .vdata
+                .line_index
+                .calculate_location(macro_call_node.text_range().start());
+            let location = FileReference::new(self.get_filename(), Some(line), Some(col));
+
+            if let Some(mut node) = RustTraceableNode::from_node_with_location(
+                macro_call_node,
+                location,
+                context.to_str(),
+            ) {
+                let count = self
+                    .vdata
+                    .macro_invocations
+                    .entry(node.name.clone())
+                    .or_insert(0);
+                *count += 1;
+                if *count > 1 {
+                    node.name = format!("{}_{}", node.name, count);
+                }
+                node.scope = context;
+                self.vdata.node_stack.push(node);
+            }
+            if self.options.parse_macro_bodies {
+                // The items of the body are visited on exit, instead of the bare token tree.
+                self.vdata.macro_body = parse_macro_body(macro_call_node);
+                if let Some(token_tree) = macro_call_node
+                    .get_child_kind(SyntaxKind::TOKEN_TREE)
+                    .filter(|_| self.vdata.macro_body.is_some())
+                {
+                    self.vdata.skipped_node = Some((token_tree.kind(), token_tree.text_range()));
+                }
+            }
+        }
+    }
+
+    /// Callback for MACRO_CALL node exit.
+    ///
+    /// Retrieves the placeholder node from the stack and appends it as a child to the enclosing
+    /// node. Only macro calls that were put on the stack as placeholder are popped.
+    /// If macro bodies are parsed, the items of the body are visited afterwards, so that they are
+    /// traced like the items next to the macro call.
+    ///
+    /// ### Parameters
+    /// * `macro_call_node` - SyntaxNode of kind MACRO_CALL.
+    fn exit_macro_call(&mut self, macro_call_node: &SyntaxNode) {
+        if !is_item_position(macro_call_node) {
+            return;
+        }
+
+        let macro_body = self.vdata.macro_body.take();
+        if let Some(closed_macro) = self.vdata.pop_kind(NodeKind::MacroInvocation) {
+            if let Some(enclosing_node) = self.vdata.node_stack.last_mut() {
+                enclosing_node.append_child(closed_macro);
+            }
+            if let Some(macro_body) = macro_body {
+                // Offsets of the body items are relative to the body, nested bodies included.
+                let base = self.vdata.line_index.base;
+                self.vdata.line_index.base = base + macro_body.offset;
+                for item in macro_body.items() {
+                    self.travel(&item);
+                }
+                self.vdata.line_index.base = base;
+            }
+        }
     }
 
     /*********************** Token visit functions ********************** */
 
     /// Callback for FN_KW token visit.
     ///
-    /// Set the correct position for the enclosing function node.
+    /// Set the correct position for the enclosing function node, at the keyword or the name.
     ///
     /// ### Parameters
     /// * `fn_keyword_token` - Token of kind FN_KW.
     fn visit_fn_keyword(&mut self, fn_keyword_token: &SyntaxToken) {
-        let (line, column) = self
-            .vdata
-            .whitespace_data
-            .calculate_token_location(fn_keyword_token);
+        // Function pointer types (`fn()`) use the fn keyword as well, but do not define a function.
+        let Some(fn_node) = fn_keyword_token
+            .parent()
+            .filter(|p| SyntaxKind::FN == p.kind())
+        else {
+            return;
+        };
+
+        let (line, column) = self.item_location(&fn_node, fn_keyword_token.text_range().start());
 
         // Get enclosing function node.
-        let enclosing_node = self.vdata.node_stack.last_mut().unwrap();
-        if NodeKind::Function == enclosing_node.kind {
+        let enclosing_node = self.vdata.node_stack.last_mut();
+        if let Some(enclosing_node) = enclosing_node.filter(|n| NodeKind::Function == n.kind) {
             enclosing_node
                 .location
                 .set_position(Some(line), Some(column));
         } else {
-            println!(
-                "WARNING: Parsed fn_kw not in function node. @{},{}",
-                line, column
+            self.report_parse_error(
+                fn_keyword_token.text_range().start(),
+                "Parsed fn_kw not in function node".to_string(),
             );
         }
     }
 
-    /// Callback for STRUCT_KW token visit.
+    /// Callback for BANG token visit.
     ///
-    /// Set the correct position for the enclosing struct node.
+    /// Set the correct position for the enclosing macro invocation node. The position is the
+    /// start of the macro path, e.g. of `make_fn` in `make_fn!(name);`.
     ///
     /// ### Parameters
-    /// * `struct_keyword_token` - Token of kind FN_KW.
-    fn visit_struct_keyword(&mut self, struct_keyword_token: &SyntaxToken) {
+    /// * `bang_token` - Token of kind BANG.
+    fn visit_bang(&mut self, bang_token: &SyntaxToken) {
+        let Some(macro_call_node) = bang_token
+            .parent()
+            .filter(|p| SyntaxKind::MACRO_CALL == p.kind())
+        else {
+            return;
+        };
+        let Some(path_token) = macro_call_node
+            .get_child_kind(SyntaxKind::PATH)
+            .and_then(|path_node| path_node.first_token())
+        else {
+            return;
+        };
         let (line, column) = self
             .vdata
-            .whitespace_data
-            .calculate_token_location(struct_keyword_token);
+            .line_index
+            .calculate_location(path_token.text_range().start());
 
-        // Get enclosing function node.
-        let enclosing_node = self.vdata.node_stack.last_mut().unwrap();
-        if NodeKind::Struct == enclosing_node.kind {
+        // Get enclosing macro invocation node.
+        let enclosing_node = self.vdata.node_stack.last_mut();
+        if let Some(enclosing_node) = enclosing_node.filter(|n| NodeKind::MacroInvocation == n.kind)
+        {
             enclosing_node
                 .location
                 .set_position(Some(line), Some(column));
-        } else {
-            println!(
-                "WARNING: Parsed struct_kw not in struct node. @{},{}",
-                line, column
-            );
         }
     }
 
-    /// Callback for WHITESPACE token visit.
+    /// Check if a node kind is a member whose annotations are parsed on exit of its owner.
     ///
-    /// Parsed the contents of the WHITESPACE token to track linebreaks in the file.
+    /// Fields are members of structs. Variants are members of enums if enums are traced.
     ///
     /// ### Parameters
-    /// * `whitespace_token` - Token of kind WHITESPACE.
-    fn visit_whitespace(&mut self, whitespace_token: &SyntaxToken) {
-        // Update whitespace data to hold current line and charpos of last linebreak
-        let ws_data = &mut self.vdata.whitespace_data;
-
-        let linebreaks = whitespace_token
-            .text()
-            .chars()
-            .filter(|c| '\n' == *c)
-            .count();
-        ws_data.current_line += linebreaks;
-
-        if let Some((lbpos, _)) = whitespace_token
-            .text()
-            .char_indices()
-            .filter(|(_, c)| '\n' == *c)
-            .last()
-        {
-            ws_data.last_linebrk = usize::from(whitespace_token.text_range().start()) + lbpos;
+    /// * `kind` - Kind of the parent node of a comment or attribute.
+    ///
+    /// ### Returns
+    /// true if the node is a member, false otherwise.
+    fn is_member(&self, kind: Option<SyntaxKind>) -> bool {
+        match kind {
+            Some(SyntaxKind::RECORD_FIELD) => true,
+            Some(SyntaxKind::VARIANT) => self.options.trace_enums,
+            _ => false,
         }
     }
 
     /// Callback for COMMENT token visit.
     ///
-    /// Parsed the contents of the COMMENT token.
-    /// Possible requirement references or justifications are found by regex application.
-    /// If a reference or justification is found, it is added to the enclosing node (from the node
-    /// stack).
+    /// Parsed the contents of the COMMENT token for annotations of the enclosing node.
     ///
     /// ### Parameters
     /// * `comment_token` - Token of kind COMMENT.
     fn visit_comment(&mut self, comment_token: &SyntaxToken) {
-        // Parse comment for lobster trace or justification annotations
-        if let Some(cnode) = self.vdata.node_stack.last_mut() {
-            let trace_re = Regex::new(r"lobster-trace: (?<ref>[[:alnum:]\._-]+)").unwrap();
-            let just_re = Regex::new(r"lobster-exclude: (?<just>[[:alnum:]\._-]+)").unwrap();
-
-            if let Some(cap) = trace_re.captures(comment_token.text()) {
-                if let Some(refmatch) = cap.name("ref") {
-                    let mut refstring = refmatch.as_str().to_string();
-                    refstring.insert_str(0, "req ");
-                    cnode.refs.push(refstring);
-                }
+        // Doc comments of fields and variants annotate the member, they are parsed on struct or
+        // enum exit.
+        let parent_kind = comment_token.parent().map(|p| p.kind());
+        if self.is_member(parent_kind) && is_doc_comment(comment_token) {
+            return;
+        }
+        // Inner doc comments of the file annotate the file as a whole.
+        if Some(SyntaxKind::SOURCE_FILE) == parent_kind && is_inner_doc_comment(comment_token) {
+            self.annotate_file(comment_token.text());
+            return;
+        }
+        self.annotate(comment_token.text());
+    }
+
+    /// Callback for ATTR node enter.
+    ///
+    /// Parses the text of doc attributes (`#[doc = "..."]`) and the references of trace attributes
+    /// (`#[lobster::trace(...)]`) as annotations of the enclosing node, like comments. As
+    /// attributes and comments of an item are visited in source order, their annotations are
+    /// added in source order as well.
+    ///
+    /// ### Parameters
+    /// * `attr_node` - SyntaxNode of kind ATTR.
+    fn enter_attr(&mut self, attr_node: &SyntaxNode) {
+        // Attributes of fields and variants annotate the member, they are parsed on struct or enum
+        // exit.
+        let parent_kind = attr_node.parent().map(|p| p.kind());
+        if self.is_member(parent_kind) {
+            return;
+        }
+        // Inner attributes of the file annotate the file as a whole, like `//!` comments.
+        let is_inner = !attr_node.get_tokens_kind(SyntaxKind::BANG).is_empty();
+        for annotation in attribute_annotations(attr_node) {
+            if Some(SyntaxKind::SOURCE_FILE) == parent_kind && is_inner {
+                self.annotate_file(&annotation);
+            } else {
+                self.annotate(&annotation);
             }
-            if let Some(cap) = just_re.captures(comment_token.text()) {
-                if let Some(justmatch) = cap.name("just") {
-                    let juststring = justmatch.as_str().to_string();
-                    cnode.just.push(juststring);
-                }
+        }
+    }
+
+    /// Convert a node holding annotations of the file to the module node of the file.
+    ///
+    /// The module node is named after the file, e.g. `main.sub` for sub.rs, and located at the
+    /// start of the file.
+    ///
+    /// ### Parameters
+    /// * `annotations` - Node holding the annotations, e.g. of the module declaration.
+    ///
+    /// ### Returns
+    /// RustTraceableNode of kind Module with the annotations.
+    fn to_file_module(&self, mut annotations: RustTraceableNode) -> RustTraceableNode {
+        let context = self.get_file_root_context();
+        annotations.kind = NodeKind::Module;
+        annotations.name = context.to_str();
+        annotations.location = FileReference::new(self.get_filename(), Some(1), Some(1));
+        annotations.scope = if self.options.file_as_context {
+            Context::Empty
+        } else {
+            self.default_context.clone()
+        };
+        annotations
+    }
+
+    /// Add the annotations of an inner doc comment to the module node of the file.
+    ///
+    /// The module node is the first child of the root node. It is created if the file has none
+    /// yet, i.e. if the module declaration is not annotated. Comments without annotations do not
+    /// create a module node.
+    ///
+    /// ### Parameters
+    /// * `text` - Text of the inner doc comment or inner doc attribute.
+    fn annotate_file(&mut self, text: &str) {
+        let mut annotations = RustTraceableNode::new_empty_context();
+        annotate_node(&mut annotations, text, self.options.strip_prefix.as_deref());
+        if !annotations.is_traced() && !annotations.nocheck {
+            return;
+        }
+        let module = self.to_file_module(annotations);
+        let Some(root_node) = self.vdata.node_stack.first_mut() else {
+            return;
+        };
+        match root_node
+            .children
+            .iter_mut()
+            .find(|child| NodeKind::Module == child.kind && child.name == module.name)
+        {
+            Some(file_module) => file_module.merge_annotations(module),
+            None => root_node.children.insert(0, module),
+        }
+    }
+
+    /// Add the annotations of a comment to the enclosing node.
+    ///
+    /// The enclosing node is the last node of the node stack.
+    ///
+    /// ### Parameters
+    /// * `text` - Text of the comment or doc attribute.
+    fn annotate(&mut self, text: &str) {
+        if let Some(cnode) = self.vdata.node_stack.last_mut() {
+            annotate_node(cnode, text, self.options.strip_prefix.as_deref());
+        }
+    }
+}
+
+/// Get the project root of a source directory.
+///
+/// The project root is the package directory holding the source directory, e.g. the directory of
+/// Cargo.toml for `src`. Paths of source files are reported relative to it.
+///
+/// ### Parameters
+/// * `source_dir` - Source directory, e.g. the directory of main.rs.
+///
+/// ### Returns
+/// Some canonical path of the project root, None if it does not exist.
+pub fn project_root(source_dir: &Path) -> Option<PathBuf> {
+    fs::canonicalize(source_dir.join("..")).ok()
+}
+
+/// Add the annotations of a comment to a node.
+///
+/// Possible requirement references or justifications are found by regex application.
+/// If a reference or justification is found, it is added to the node. A `lobster-nocheck` comment
+/// exempts the node from the coverage gate.
+///
+/// ### Parameters
+/// * `cnode` - Node to annotate.
+/// * `text` - Text of the comment or doc attribute.
+/// * `strip_prefix` - Optional prefix removed from the reference.
+fn annotate_node(cnode: &mut RustTraceableNode, text: &str, strip_prefix: Option<&str>) {
+    // Parse comment for lobster trace or justification annotations
+    if let Some(refstring) = parse_trace_ref(text, strip_prefix) {
+        cnode.refs.push(refstring);
+    }
+    if NOCHECK_RE.is_match(text) {
+        cnode.nocheck = true;
+    }
+    if let Some(cap) = JUST_RE.captures(text) {
+        if let Some(justmatch) = cap.name("just") {
+            // A quoted reason is appended to the justification, e.g. `ID: reason`.
+            let juststring = match cap.name("reason") {
+                Some(reason) => format!("{}: {}", justmatch.as_str(), reason.as_str()),
+                None => justmatch.as_str().to_string(),
+            };
+            // Justifications without explicit direction are upwards justifications.
+            match cap.name("direction").map(|d| d.as_str()) {
+                Some("down") => cnode.just_down.push(juststring),
+                Some("global") => cnode.just_global.push(juststring),
+                _ => cnode.just_up.push(juststring),
             }
         }
     }
@@ -577,53 +1821,93 @@ impl RustVisitor {
 impl Visitor for RustVisitor {
     /// Callback for node enter.
     ///
-    /// Calls the specific callback for the SyntaxKind of the node.
+    /// Calls the specific callback for the SyntaxKind of the node, followed by the extensions.
+    /// Nodes within skipped test-only code are ignored. If only the modules are resolved, all nodes
+    /// except those that can hold module declarations are skipped.
     ///
     /// ### Parameters
     /// * `node` - Syntax node that is visited.
     fn node_enter(&mut self, node: &SyntaxNode) {
+        if self.vdata.skipped_node.is_some() {
+            return;
+        }
+        if self.options.modules_only && !MODULE_STRUCTURE.contains(&node.kind()) {
+            // Items can not declare modules, their content is not parsed.
+            self.vdata.skipped_node = Some((node.kind(), node.text_range()));
+            return;
+        }
         match node.kind() {
             SyntaxKind::SOURCE_FILE => self.enter_source(node),
             SyntaxKind::FN => self.enter_fn(node),
             SyntaxKind::STRUCT => self.enter_struct(node),
+            SyntaxKind::ENUM if self.options.trace_enums => self.enter_enum(node),
             SyntaxKind::IMPL => self.enter_impl(node),
             SyntaxKind::MODULE => self.enter_module(node),
             SyntaxKind::TRAIT => self.enter_trait(node),
+            SyntaxKind::MACRO_CALL => self.enter_macro_call(node),
+            SyntaxKind::MACRO_DEF => self.enter_macro_def(node),
+            SyntaxKind::USE => self.enter_use(node),
+            SyntaxKind::ATTR => self.enter_attr(node),
             _ => (),
         }
+        if self.vdata.skipped_node.is_none() {
+            for extension in self.options.extensions.iter() {
+                extension.node_enter(node, self.vdata.node_stack.last_mut());
+            }
+        }
     }
 
     /// Callback for node exit.
     ///
-    /// Calls the specific callback for the SyntaxKind of the node.
+    /// Calls the extensions, followed by the specific callback for the SyntaxKind of the node.
+    /// Nodes within skipped test-only code are ignored.
     ///
     /// ### Parameters
     /// * `node` - Syntax node that was visited.
     fn node_exit(&mut self, node: &SyntaxNode) {
+        if let Some(skipped_node) = self.vdata.skipped_node {
+            if skipped_node == (node.kind(), node.text_range()) {
+                self.vdata.skipped_node = None;
+            }
+            return;
+        }
+        for extension in self.options.extensions.iter() {
+            extension.node_exit(node, self.vdata.node_stack.last_mut());
+        }
         match node.kind() {
             SyntaxKind::FN => self.exit_fn(node),
             SyntaxKind::STRUCT => self.exit_struct(node),
+            SyntaxKind::ENUM if self.options.trace_enums => self.exit_enum(node),
             SyntaxKind::IMPL => self.exit_impl(node),
             SyntaxKind::MODULE => self.exit_module(node),
             SyntaxKind::TRAIT => self.exit_trait(node),
+            SyntaxKind::MACRO_CALL => self.exit_macro_call(node),
+            SyntaxKind::MACRO_DEF => self.exit_macro_def(node),
+            SyntaxKind::USE => self.exit_use(node),
             _ => (),
         }
     }
 
     /// Callback for token visit.
     ///
-    /// Calls the specific callback for the SyntaxKind of the token.
+    /// Calls the specific callback for the SyntaxKind of the token, followed by the extensions.
+    /// Tokens within skipped test-only code are ignored.
     ///
     /// ### Parameters
     /// * `token` - Syntax token that is visited.
     fn token_visit(&mut self, token: &SyntaxToken) {
+        if self.vdata.skipped_node.is_some() {
+            return;
+        }
         match token.kind() {
-            SyntaxKind::WHITESPACE => self.visit_whitespace(token),
             SyntaxKind::COMMENT => self.visit_comment(token),
             SyntaxKind::FN_KW => self.visit_fn_keyword(token),
-            SyntaxKind::STRUCT_KW => self.visit_struct_keyword(token),
+            SyntaxKind::BANG => self.visit_bang(token),
             _ => (),
         }
+        for extension in self.options.extensions.iter() {
+            extension.token_visit(token, self.vdata.node_stack.last_mut());
+        }
     }
 
     /// Visit the source tree defined by the root node.
@@ -637,3 +1921,51 @@ impl Visitor for RustVisitor {
         root.visit(self);
     }
 }
+
+/// Parse the requirement reference of a `lobster-trace` comment.
+///
+/// The kind of the requirement can be given in parentheses, e.g.
+/// `lobster-trace(design): something.example`, and is used as namespace of the reference.
+/// Without kind, the reference is in the `req` namespace. References starting with the strip
+/// prefix are emitted without it, other references are kept unchanged.
+///
+/// ### Parameters
+/// * `comment` - Text of the comment.
+/// * `strip_prefix` - Optional prefix removed from the reference, e.g. `CompanyProj.`.
+///
+/// ### Returns
+/// Some reference (e.g. `req something.example`) if the comment holds a trace annotation.
+fn parse_trace_ref(comment: &str, strip_prefix: Option<&str>) -> Option<String> {
+    let cap = TRACE_RE.captures(comment)?;
+    let refmatch = cap.name("ref")?;
+    let kind = cap.name("kind").map_or("req", |kind| kind.as_str());
+    let reference = strip_prefix
+        .and_then(|prefix| refmatch.as_str().strip_prefix(prefix))
+        .unwrap_or(refmatch.as_str());
+    Some(format!("{} {}", kind, reference))
+}
+
+/// Check if a comment is an inner doc comment (`//!` or `/*!`).
+///
+/// ### Parameters
+/// * `comment_token` - Token of kind COMMENT.
+///
+/// ### Returns
+/// true if the comment is an inner doc comment.
+fn is_inner_doc_comment(comment_token: &SyntaxToken) -> bool {
+    let text = comment_token.text();
+    text.starts_with("//!") || text.starts_with("/*!")
+}
+
+/// Check if a comment is an outer doc comment (`///` or `/**`).
+///
+/// ### Parameters
+/// * `comment_token` - Token of kind COMMENT.
+///
+/// ### Returns
+/// true if the comment is an outer doc comment.
+fn is_doc_comment(comment_token: &SyntaxToken) -> bool {
+    let text = comment_token.text();
+    (text.starts_with("///") && !text.starts_with("////"))
+        || (text.starts_with("/**") && !text.starts_with("/***"))
+}
@@ -0,0 +1,164 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! YAML serialization of lobster documents.
+//!
+//! The documents are built as JsonValue and serialized to YAML with the same field names and
+//! structure. Objects and arrays are written in block style, strings are always double-quoted so
+//! that values like `null` or `1.0` keep their type. JSON string escapes are valid YAML escapes in
+//! double-quoted strings, so strings are quoted like in the JSON output.
+
+use json::JsonValue;
+
+/// Serialize a JsonValue to YAML.
+///
+/// ### Parameters
+/// * `value` - JsonValue to serialize, e.g. a full lobster document.
+///
+/// ### Returns
+/// YAML representation of the value, ending with a newline.
+pub fn to_yaml(value: &JsonValue) -> String {
+    let mut out = String::new();
+    if is_block(value) {
+        write_block(&mut out, value, 0);
+    } else {
+        out.push_str(&scalar(value));
+        out.push('\n');
+    }
+    out
+}
+
+/// Write a non-empty object or array in block style.
+///
+/// Every entry is written on its own line, starting with the given indentation. Nested objects
+/// and arrays are indented by two more spaces. The first entry of an object or array in an array
+/// follows the dash of its array entry, e.g. `- tag: "rust main.main"`.
+///
+/// ### Parameters
+/// * `out` - String the YAML is appended to.
+/// * `value` - Object or array to write.
+/// * `indent` - Number of spaces to indent the entries with.
+fn write_block(out: &mut String, value: &JsonValue, indent: usize) {
+    let pad = " ".repeat(indent);
+    match value {
+        JsonValue::Object(object) => {
+            for (key, item) in object.iter() {
+                out.push_str(&pad);
+                out.push_str(&yaml_key(key));
+                out.push(':');
+                write_entry(out, item, indent + 2, true);
+            }
+        }
+        JsonValue::Array(items) => {
+            for item in items.iter() {
+                out.push_str(&pad);
+                out.push('-');
+                write_entry(out, item, indent + 2, false);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Write the value of an object or array entry, after the key or dash.
+///
+/// ### Parameters
+/// * `out` - String the YAML is appended to.
+/// * `item` - Value of the entry.
+/// * `indent` - Indentation of nested entries.
+/// * `after_key` - The value follows a key, nested blocks start on the next line.
+fn write_entry(out: &mut String, item: &JsonValue, indent: usize, after_key: bool) {
+    if !is_block(item) {
+        out.push(' ');
+        out.push_str(&scalar(item));
+        out.push('\n');
+    } else if after_key {
+        out.push('\n');
+        write_block(out, item, indent);
+    } else {
+        // The first line of the nested block follows the dash.
+        let mut nested = String::new();
+        write_block(&mut nested, item, indent);
+        out.push(' ');
+        out.push_str(&nested[indent..]);
+    }
+}
+
+/// Check if a value is written in block style, i.e. a non-empty object or array.
+///
+/// ### Parameters
+/// * `value` - Value to check.
+///
+/// ### Returns
+/// true if the value is a non-empty object or array.
+fn is_block(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Object(object) => !object.is_empty(),
+        JsonValue::Array(items) => !items.is_empty(),
+        _ => false,
+    }
+}
+
+/// Get the YAML representation of a scalar, an empty object or an empty array.
+///
+/// ### Parameters
+/// * `value` - Value to represent.
+///
+/// ### Returns
+/// The value in flow style, strings double-quoted.
+fn scalar(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Object(_) => "{}".to_string(),
+        JsonValue::Array(_) => "[]".to_string(),
+        // null, booleans, numbers and quoted strings are written like in JSON.
+        _ => value.dump(),
+    }
+}
+
+/// Get the YAML representation of an object key.
+///
+/// Keys consisting of letters, digits and underscores are written plain, other keys (e.g.
+/// `$schema`) and keys that YAML parsers read as null or boolean are double-quoted.
+///
+/// ### Parameters
+/// * `key` - Key of an object entry.
+///
+/// ### Returns
+/// The key as plain or double-quoted YAML scalar.
+fn yaml_key(key: &str) -> String {
+    let plain = key.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || '_' == c);
+    let reserved = ["null", "true", "false", "yes", "no", "on", "off"]
+        .contains(&key.to_ascii_lowercase().as_str());
+    if plain && !reserved {
+        key.to_string()
+    } else {
+        JsonValue::from(key).dump()
+    }
+}
@@ -0,0 +1,61 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Tests for the Context used to build the names of traceable nodes.
+
+use lobster_rust::utils::context::Context;
+use std::sync::Arc;
+
+/// Build a stacked Context from the given namespaces.
+fn stacked(namespaces: &[&str]) -> Context {
+    Context::Stacked(namespaces.iter().map(|n| Arc::from(*n)).collect())
+}
+
+#[test]
+fn depth_counts_namespaces() {
+    assert_eq!(Context::Empty.depth(), 0);
+    assert_eq!(stacked(&["main", "a", "b"]).depth(), 3);
+}
+
+#[test]
+fn truncate_keeps_innermost_namespaces() {
+    let context = stacked(&["main", "a", "b", "c"]);
+    assert_eq!(context.truncate(2).to_str(), "b.c");
+    assert_eq!(context.truncate(4).to_str(), "main.a.b.c");
+    assert_eq!(context.truncate(10).to_str(), "main.a.b.c");
+}
+
+#[test]
+fn truncate_to_zero_is_empty() {
+    assert!(matches!(
+        stacked(&["main", "a"]).truncate(0),
+        Context::Empty
+    ));
+    assert!(matches!(Context::Empty.truncate(2), Context::Empty));
+}
@@ -0,0 +1,107 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Micro-benchmark for the allocations of Context operations on deeply nested namespaces.
+//!
+//! The allocations are counted per thread by a wrapping global allocator, so tests running in
+//! parallel do not influence each other.
+
+use lobster_rust::utils::context::Context;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::Arc;
+
+/// Depth of the nested namespaces, e.g. a module nested 64 levels deep.
+const DEPTH: usize = 64;
+
+/// Global allocator counting the allocations of the current thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|a| a.set(a.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|a| a.set(a.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Count the allocations done by the given closure on the current thread.
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(|a| a.get());
+    let result = f();
+    (result, ALLOCATIONS.with(|a| a.get()) - before)
+}
+
+/// Build one single namespace Context per nesting level.
+fn levels() -> Vec<Context> {
+    (0..DEPTH)
+        .map(|i| Context::Stacked(Arc::from([Arc::from(format!("module{i}"))])))
+        .collect()
+}
+
+#[test]
+fn clone_does_not_allocate() {
+    let deep: Context = levels().iter().sum();
+    let (clone, allocations) = count_allocations(|| deep.clone());
+    assert_eq!(clone.depth(), DEPTH);
+    assert_eq!(allocations, 0);
+}
+
+#[test]
+fn combine_allocates_only_the_new_stack() {
+    let deep: Context = levels().iter().sum();
+    let (combined, allocations) = count_allocations(|| &deep + &deep);
+    assert_eq!(combined.depth(), 2 * DEPTH);
+    assert_eq!(allocations, 1);
+}
+
+#[test]
+fn sum_does_not_allocate_per_level() {
+    let levels = levels();
+    let (deep, allocations) = count_allocations(|| levels.iter().sum::<Context>());
+    assert_eq!(deep.depth(), DEPTH);
+    assert!(deep.to_str().ends_with(".module63"));
+    // Growing the collected stack reallocates logarithmically, not once per level.
+    assert!(allocations < DEPTH / 4, "{allocations} allocations");
+}
@@ -0,0 +1,170 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Tests for the errors collected by the RustVisitor while parsing.
+
+use lobster_rust::error::LobsterError;
+use lobster_rust::utils::context::Context;
+use lobster_rust::visitor::{RustVisitor, VisitorOptions};
+use std::path::PathBuf;
+
+/// Get the path of the main.rs of a fixture.
+fn fixture_main(fixture: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(fixture)
+        .join("src/main.rs")
+}
+
+/// Parse a file and return the errors found while parsing it.
+fn parse_errors(filepath: PathBuf) -> Vec<LobsterError> {
+    parse_errors_with(filepath, VisitorOptions::default())
+}
+
+/// Parse a file with the given options and return the errors found while parsing it.
+fn parse_errors_with(filepath: PathBuf, options: VisitorOptions) -> Vec<LobsterError> {
+    let mut visitor = RustVisitor::new(filepath, Context::Empty, options);
+    visitor.parse_file();
+    visitor.errors().into_iter().cloned().collect()
+}
+
+#[test]
+fn valid_file_produces_no_errors() {
+    assert_eq!(parse_errors(fixture_main("functions")), []);
+}
+
+#[test]
+fn missing_file_is_reported() {
+    let filepath = fixture_main("nonexistent");
+    let errors = parse_errors(filepath.clone());
+    assert!(
+        matches!(errors.as_slice(), [LobsterError::FileRead { path, .. }] if *path == filepath),
+        "{errors:?}"
+    );
+}
+
+#[test]
+fn unresolved_module_is_reported() {
+    let filepath = fixture_main("unresolved_module");
+    assert_eq!(
+        parse_errors(filepath.clone()),
+        [LobsterError::ModuleUnresolved {
+            path: filepath,
+            module: "missing".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn path_attribute_inside_project_is_not_reported() {
+    // The project root defaults to the package directory, like for the binary.
+    let filepath = fixture_main("path_parent");
+    assert_eq!(
+        parse_errors(filepath.clone()),
+        [LobsterError::ModuleUnresolved {
+            path: filepath,
+            module: "missing".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn path_attribute_outside_project_is_reported() {
+    let filepath = fixture_main("path_parent");
+    let src_dir = filepath.parent().unwrap().canonicalize().unwrap();
+    let resolved = src_dir.join("../shared/common.rs").canonicalize().unwrap();
+    let options = VisitorOptions {
+        project_root: Some(src_dir.clone()),
+        ..VisitorOptions::default()
+    };
+    assert_eq!(
+        parse_errors_with(filepath.clone(), options),
+        [
+            LobsterError::ModuleOutsideRoot {
+                path: filepath.clone(),
+                module: "common".to_string(),
+                resolved,
+                root: src_dir,
+            },
+            LobsterError::ModuleUnresolved {
+                path: filepath,
+                module: "missing".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn ambiguous_module_is_reported() {
+    let filepath = fixture_main("ambiguous_module");
+    let src_dir = filepath.parent().unwrap();
+    assert_eq!(
+        parse_errors(filepath.clone()),
+        [LobsterError::ModuleAmbiguous {
+            file_module: src_dir.join("foo.rs"),
+            directory_module: src_dir.join("foo").join("mod.rs"),
+        }]
+    );
+}
+
+#[test]
+fn malformed_impl_is_reported() {
+    let filepath = fixture_main("malformed");
+    let errors = parse_errors(filepath.clone());
+    assert!(errors.contains(&LobsterError::MalformedImpl {
+        path: filepath,
+        line: 7,
+        column: 1,
+    }));
+}
+
+#[test]
+fn syntax_errors_are_reported() {
+    let errors = parse_errors(fixture_main("malformed"));
+    assert!(errors.iter().any(
+        |e| matches!(e, LobsterError::ParseError { line: 3, column: 1, message, .. }
+            if message == "Module without name")
+    ));
+    // The syntax errors of the parser are reported as well, e.g. for the missing module name.
+    assert!(errors.iter().any(
+        |e| matches!(e, LobsterError::ParseError { line: 3, message, .. }
+            if message != "Module without name")
+    ));
+}
+
+#[test]
+fn stray_keywords_are_reported() {
+    let errors = parse_errors(fixture_main("stray_keywords"));
+    assert!(
+        errors
+            .iter()
+            .any(|e| matches!(e, LobsterError::ParseError { line: 4, .. })),
+        "{errors:?}"
+    );
+}
@@ -0,0 +1,32 @@
+// Fixture: test functions marked by different test attributes.
+
+fn add(a: u8, b: u8) -> u8 {
+    // lobster-trace: SWRS.add
+    a + b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_numbers() {
+        // lobster-trace: SWRS.add
+        assert_eq!(add(1, 2), 3);
+    }
+
+    #[tokio::test]
+    async fn adds_asynchronously() {
+        // lobster-trace: SWRS.add
+        assert_eq!(add(2, 2), 4);
+    }
+
+    #[rstest]
+    fn adds_with_fixture() {
+        assert_eq!(add(0, 0), 0);
+    }
+
+    fn helper() {}
+}
+
+fn main() {}
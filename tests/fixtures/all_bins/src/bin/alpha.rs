@@ -0,0 +1,5 @@
+// Fixture: binary as single file.
+
+fn main() {
+    // lobster-trace: example.alpha
+}
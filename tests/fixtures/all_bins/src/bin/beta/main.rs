@@ -0,0 +1,7 @@
+// Fixture: binary as directory with main file.
+
+mod util;
+
+fn main() {
+    // lobster-trace: example.beta
+}
@@ -0,0 +1,3 @@
+// Fixture: module of the binary in a directory.
+
+pub fn helper() {}
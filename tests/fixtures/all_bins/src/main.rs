@@ -0,0 +1,3 @@
+// Fixture: crate with multiple binaries.
+
+fn main() {}
@@ -0,0 +1,3 @@
+// Fixture: file module, chosen for the ambiguous declaration.
+
+pub fn from_file() {}
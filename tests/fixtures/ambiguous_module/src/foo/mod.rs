@@ -0,0 +1,3 @@
+// Fixture: directory module, ignored for the ambiguous declaration.
+
+pub fn from_directory() {}
@@ -0,0 +1,5 @@
+// Fixture: module declaration with both foo.rs and foo/mod.rs present.
+
+mod foo;
+
+fn main() {}
@@ -0,0 +1,14 @@
+/// lobster-trace: SYRS.greeting
+trait Greet {
+    fn greet(&self);
+}
+
+struct Person;
+
+impl Greet for Person {
+    fn greet(&self) {
+        // lobster-trace: main.Greet
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+// Fixture: blanket impl of a trait for all matching types.
+
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+impl<T: std::fmt::Debug> Describe for T {
+    fn describe(&self) -> String {
+        // lobster-trace: example.describe
+        format!("{:?}", self)
+    }
+}
+
+fn main() {}
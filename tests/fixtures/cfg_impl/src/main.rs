@@ -0,0 +1,15 @@
+// Fixture: methods of a cfg-gated impl block inherit its conditions.
+
+struct Foo;
+
+#[cfg(feature = "x")]
+impl Foo {
+    fn first(&self) {
+        // lobster-trace: SWRS.first
+    }
+
+    #[cfg(unix)]
+    fn second(&self) {}
+}
+
+fn main() {}
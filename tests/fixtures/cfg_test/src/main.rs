@@ -0,0 +1,13 @@
+mod tests;
+
+mod inline_tests {
+    #![cfg(test)]
+    fn check() {}
+}
+
+mod spaced_tests {
+    #![cfg( test )]
+    fn spaced() {}
+}
+
+fn main() {}
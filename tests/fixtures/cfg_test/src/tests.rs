@@ -0,0 +1,3 @@
+#![cfg(test)]
+
+fn test_main() {}
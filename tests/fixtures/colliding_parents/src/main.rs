@@ -0,0 +1,13 @@
+// Fixture: structs with the same name and methods in different modules, named via --no-context.
+
+mod sub;
+
+// lobster-trace: SWRS.config
+struct Config;
+
+impl Config {
+    // lobster-trace: SWRS.load
+    fn load(&self) {}
+}
+
+fn main() {}
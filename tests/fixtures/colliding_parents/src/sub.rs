@@ -0,0 +1,6 @@
+// lobster-trace: SWRS.sub_config
+pub struct Config;
+
+impl Config {
+    pub fn save(&self) {}
+}
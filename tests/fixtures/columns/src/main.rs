@@ -0,0 +1,3 @@
+fn first() {}
+    fn indented() {}
+struct Third;
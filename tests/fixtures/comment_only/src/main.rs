@@ -0,0 +1,4 @@
+// Fixture: a file without any items.
+// lobster-trace: example.nothing
+
+/* Block comments without items as well. */
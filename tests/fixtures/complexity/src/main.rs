@@ -0,0 +1,22 @@
+// Fixture: size of function signatures and bodies, emitted with --complexity.
+
+struct Store;
+
+impl Store {
+    fn insert<'a, T: Clone, const N: usize>(
+        &mut self,
+        key: &'a str,
+        values: [T; N],
+        flag: bool,
+    ) -> usize {
+        let count = N;
+        if flag {
+            let _ = key;
+        }
+        count
+    }
+}
+
+fn empty() {}
+
+fn main() {}
@@ -0,0 +1,12 @@
+struct Foo;
+
+impl Foo {
+    const fn size() -> usize {
+        // lobster-trace: SWRS.size
+        4
+    }
+}
+
+const SIZE: usize = Foo::size();
+
+fn main() {}
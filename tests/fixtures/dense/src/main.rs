@@ -0,0 +1,5 @@
+struct A;struct B;fn c(f: fn()){}fn d(){}
+/* A block comment
+   spanning multiple lines. */ fn e() {}
+const TEXT: &str = "a string
+spanning two lines"; fn f() {}
@@ -0,0 +1,12 @@
+// Fixture: the new state of a change, compared against before.lobster.
+
+// lobster-trace: SWRS.kept
+fn kept() {}
+
+// lobster-trace: SWRS.retraced
+fn retraced() {}
+
+// lobster-trace: SWRS.added
+fn added() {}
+
+fn main() {}
@@ -0,0 +1,19 @@
+// Fixture: justifications for each tracing direction.
+
+fn implicit() {
+    // lobster-exclude: Not_required
+}
+
+fn upwards() {
+    // lobster-exclude-up: Not_required
+}
+
+fn downwards() {
+    // lobster-exclude-down: Not_testable
+}
+
+fn global() {
+    // lobster-exclude-global: Generated
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[doc = "lobster-trace: SWRS.generated"]
+fn generated() {}
+
+#[doc = "lobster-exclude: Generated_helper"]
+fn helper() {}
+
+struct Packet {
+    #[doc = "lobster-trace: SWRS.length"]
+    length: u16,
+}
+
+fn main() {}
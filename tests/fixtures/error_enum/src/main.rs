@@ -0,0 +1,21 @@
+// Fixture: thiserror-style error enum with annotated variants.
+
+use thiserror::Error;
+
+// lobster-trace: SWRS.errors
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// lobster-trace: SWRS.missing_file
+    #[error("config file {0} not found")]
+    MissingFile(String),
+    /// lobster-trace: SWRS.invalid_value
+    #[error("invalid value for {key}: {value}")]
+    InvalidValue { key: String, value: String },
+    /// lobster-trace: SWRS.invalid_path
+    #[error("invalid path \"{0}\"")]
+    InvalidPath(String),
+    #[error("unknown error")]
+    Unknown,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+// Fixture: functions exported via no_mangle and export_name, also as unsafe attributes.
+
+#[no_mangle]
+extern "C" fn plain() {}
+
+#[unsafe(no_mangle)]
+extern "C" fn wrapped() {}
+
+#[export_name = "renamed_symbol"]
+extern "C" fn renamed() {}
+
+#[unsafe(export_name = "wrapped_symbol")]
+extern "C" fn wrapped_renamed() {}
+
+#[export_name = r"raw_symbol"]
+extern "C" fn raw() {}
+
+#[export_name = "escaped\x5fsymbol"]
+extern "C" fn escaped() {}
+
+fn main() {}
@@ -0,0 +1,5 @@
+// Fixture: build script.
+
+fn main() {
+    // lobster-trace: example.build
+}
@@ -0,0 +1,3 @@
+// Fixture: module of the standalone binary.
+
+pub fn help() {}
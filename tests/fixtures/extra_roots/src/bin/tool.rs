@@ -0,0 +1,7 @@
+// Fixture: standalone binary.
+
+mod helper;
+
+fn main() {
+    // lobster-trace: example.tool
+}
@@ -0,0 +1,3 @@
+// Fixture: additional crate roots.
+
+fn main() {}
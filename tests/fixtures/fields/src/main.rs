@@ -0,0 +1,11 @@
+struct Config {
+    /// lobster-trace: SWRS.timeout
+    timeout: u32,
+    retries: u8,
+    /// The name of the device.
+    name: String,
+    /// lobster-exclude: Internal_cache
+    cache: Vec<u8>,
+}
+
+fn main() {}
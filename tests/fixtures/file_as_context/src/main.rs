@@ -0,0 +1,8 @@
+// Fixture: items of every file are named after the file with --file-as-context.
+
+mod sub;
+
+// lobster-trace: SWRS.top
+fn top() {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+mod nested;
+
+struct Settings;
+
+impl Settings {
+    // lobster-trace: SWRS.load
+    fn load(&self) {}
+}
+
+fn helper() {}
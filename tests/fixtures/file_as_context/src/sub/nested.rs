@@ -0,0 +1,2 @@
+// lobster-trace: SWRS.deep
+fn deep() {}
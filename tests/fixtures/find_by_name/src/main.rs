@@ -0,0 +1,9 @@
+mod inner {
+    struct Foo;
+
+    impl Foo {
+        fn bar() {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+// Fixture: function qualifiers.
+
+async fn fetch() {
+    // lobster-trace: example.async
+}
+
+const fn answer() -> u32 {
+    42
+}
+
+unsafe fn raw() {}
+
+async unsafe fn async_raw() {}
+
+const unsafe fn const_raw() {}
+
+struct Service;
+
+impl Service {
+    pub async fn serve(&self) {}
+}
+
+fn main() {}
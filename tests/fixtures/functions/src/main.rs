@@ -0,0 +1,19 @@
+// Fixture: traced, justified and exported functions.
+
+fn traced() {
+    // lobster-trace: example.traced
+}
+
+fn justified() {
+    // lobster-exclude: Not_required
+    // lobster-exclude-down: Not_testable
+    // lobster-exclude-global: Generated
+}
+
+#[no_mangle]
+pub extern "C" fn exported() {
+    // lobster-trace: example.exported
+    // lobster-trace: example.interface
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+// Fixture: trace annotations on generic bounds.
+
+trait SafetyTrait {}
+
+fn guarded<T>(value: T) -> T
+where
+    // lobster-trace: SWRS.safety_bound
+    T: SafetyTrait,
+{
+    value
+}
+
+fn generic<
+    // lobster-trace: SWRS.generic_param
+    T: SafetyTrait + Clone,
+>(
+    value: &T,
+) -> T {
+    value.clone()
+}
+
+fn main() {}
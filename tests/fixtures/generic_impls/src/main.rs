@@ -0,0 +1,54 @@
+// Fixture: impl blocks with const generics and generic associated types.
+
+struct Buffer<const N: usize>;
+
+impl<const N: usize> Buffer<N> {
+    // lobster-trace: SWRS.capacity
+    fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl Buffer<4> {
+    // lobster-trace: SWRS.quad
+    fn quad() -> Self {
+        Buffer
+    }
+}
+
+impl Buffer<8> {
+    // lobster-trace: SWRS.octa
+    fn quad() -> Self {
+        Buffer
+    }
+}
+
+trait Lending {
+    type Item<'a>
+    where
+        Self: 'a;
+
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+}
+
+struct Window<T> {
+    items: Vec<T>,
+}
+
+impl<T> Lending for Window<T> {
+    type Item<'a> = &'a [T] where T: 'a;
+
+    // lobster-trace: SWRS.next
+    fn next<'a>(&'a mut self) -> Option<Self::Item<'a>> {
+        Some(self.items.as_slice())
+    }
+}
+
+impl<T: Clone, const N: usize> From<[T; N]> for Window<T> {
+    // lobster-trace: SWRS.from
+    fn from(items: [T; N]) -> Self {
+        Window { items: items.to_vec() }
+    }
+}
+
+fn main() {}
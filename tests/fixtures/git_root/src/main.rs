@@ -0,0 +1,5 @@
+// Fixture: file paths relative to the git repository root, copied into a repository by the test.
+
+mod sub;
+
+fn main() {}
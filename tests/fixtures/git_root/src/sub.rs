@@ -0,0 +1,3 @@
+// Fixture: submodule located in its own file.
+
+pub fn helper() {}
@@ -0,0 +1,3 @@
+// Fixture: ignored binary.
+
+fn main() {}
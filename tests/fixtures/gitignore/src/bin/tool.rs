@@ -0,0 +1,3 @@
+// Fixture: binary that is not ignored.
+
+fn main() {}
@@ -0,0 +1,3 @@
+// Fixture: ignored generated module.
+
+pub fn generated() {}
@@ -0,0 +1,3 @@
+// Fixture: module that is not ignored.
+
+pub fn kept() {}
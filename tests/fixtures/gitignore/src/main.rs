@@ -0,0 +1,6 @@
+// Fixture: files ignored by the .gitignore are skipped with --respect-gitignore.
+
+mod generated;
+mod kept;
+
+fn main() {}
@@ -0,0 +1,13 @@
+trait Describe {
+    fn describe(&self);
+}
+
+impl Describe for (u8, u8) {
+    fn describe(&self) {}
+}
+
+impl Describe for [u8; 4] {
+    fn describe(&self) {}
+}
+
+fn main() {}
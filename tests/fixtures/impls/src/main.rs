@@ -0,0 +1,33 @@
+// Fixture: impl block variants and trait default methods.
+
+struct Wrapper<'a> {
+    // lobster-trace: example.wrapper
+    value: &'a str,
+}
+
+impl<'a> Wrapper<'a> {
+    fn value(&self) -> &'a str {
+        // lobster-trace: example.value
+        self.value
+    }
+}
+
+trait Describe {
+    fn name(&self) -> String;
+
+    fn describe(&self) -> String {
+        // lobster-trace: example.describe
+        self.name()
+    }
+}
+
+impl<T: std::fmt::Debug> Describe for T {
+    fn name(&self) -> String {
+        // lobster-trace: example.blanket
+        format!("{:?}", self)
+    }
+}
+
+impl !Send for Wrapper<'_> {}
+
+fn main() {}
@@ -0,0 +1,5 @@
+// File included via a string literal with an escape sequence.
+
+fn escaped() {
+    // lobster-trace: example.escaped
+}
@@ -0,0 +1,7 @@
+// Fixture: files included via a raw string literal and a string literal with an escape.
+
+include!(r"raw.rs");
+
+include!("escaped\x2Ers");
+
+fn main() {}
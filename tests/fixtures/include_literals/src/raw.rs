@@ -0,0 +1,5 @@
+// File included via a raw string literal.
+
+fn raw() {
+    // lobster-trace: example.raw
+}
@@ -0,0 +1,7 @@
+//! Fixture: inner doc comments trace the file as a module.
+//! lobster-trace: SWRS.module
+#![doc = "lobster-exclude: WHOLE_FILE"]
+
+fn main() {
+    // lobster-trace: SWRS.main
+}
@@ -0,0 +1,5 @@
+// Listed, but not declared by any module.
+
+fn generated() {
+    // lobster-trace: SWRS.generated
+}
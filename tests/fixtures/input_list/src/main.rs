@@ -0,0 +1,7 @@
+// Fixture: only the listed files are parsed, module declarations are not followed.
+
+mod sub;
+
+fn main() {
+    // lobster-trace: SWRS.main
+}
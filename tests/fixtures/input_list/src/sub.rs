@@ -0,0 +1,3 @@
+// Not listed, must not be parsed although it is declared.
+
+fn unlisted() {}
@@ -0,0 +1,15 @@
+// Fixture: justifications with and without quoted reason.
+
+fn debug_dump() {
+    // lobster-exclude: DEBUG_ONLY "Only used while debugging, not part of the product"
+}
+
+fn legacy() {
+    // lobster-exclude-down: LEGACY "Covered by the system tests of the old release"
+}
+
+fn plain() {
+    // lobster-exclude: NO_REQUIREMENT
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+// Fixture: impl targets that only carry lifetime arguments.
+
+struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(&self) -> &'a str {
+        // lobster-trace: example.parse
+        self.input
+    }
+}
+
+impl Parser<'static> {
+    fn constant() -> Self {
+        // lobster-trace: example.constant
+        Parser { input: "" }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+fn outer() {
+    struct Local;
+
+    impl Local {
+        fn method(&self) {
+            // lobster-trace: SWRS.local
+        }
+    }
+
+    Local.method();
+}
+
+fn main() {}
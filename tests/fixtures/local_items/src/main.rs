@@ -0,0 +1,29 @@
+fn first() {
+    // lobster-trace: SWRS.first
+
+    struct Local;
+
+    impl Local {
+        fn method(&self) {
+            // lobster-trace: SWRS.first_method
+        }
+    }
+
+    Local.method();
+}
+
+fn second() {
+    // lobster-trace: SWRS.second
+
+    struct Local;
+
+    impl Local {
+        fn method(&self) {
+            // lobster-trace: SWRS.second_method
+        }
+    }
+
+    Local.method();
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+// Fixture: items defined in the bodies of macro calls, traced via --parse-macro-bodies.
+
+macro_rules! items {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+/// lobster-trace: SWRS.items
+items! {
+    // lobster-trace: SWRS.generated
+    fn generated() {}
+
+    struct Generated;
+}
+
+thread_local! {
+    static COUNTER: u32 = 0;
+}
+
+fn main() {}
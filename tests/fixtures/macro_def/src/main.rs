@@ -0,0 +1,16 @@
+// Fixture: declarative macros 2.0 in the entry file and in a module.
+#![feature(decl_macro)]
+
+/// lobster-trace: SWRS.square
+pub macro square($x:expr) {
+    $x * $x
+}
+
+mod inner {
+    macro double($x:expr) {
+        // lobster-trace: SWRS.double
+        $x + $x
+    }
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+// Fixture: macro invocations at item position.
+
+macro_rules! make_fn {
+    ($name:ident) => {
+        fn $name() {}
+    };
+}
+
+make_fn!(generated);
+
+/// lobster-trace: example.generated
+make_fn!(other);
+
+make_fn!(generated_again);
+
+struct Foo;
+
+impl Foo {
+    make_fn!(method);
+}
+
+fn main() {
+    println!("not at item position");
+}
@@ -0,0 +1,17 @@
+// Fixture: malformed items must not abort the parsing.
+
+mod {
+    fn in_unnamed_module() {}
+}
+
+impl {
+    fn in_malformed_impl() {}
+}
+
+trait {
+    fn in_unnamed_trait() {}
+}
+
+mod
+
+fn main() {}
@@ -0,0 +1,10 @@
+mod a {
+    pub mod b {
+        pub mod c {
+            pub fn deep() {}
+        }
+    }
+    pub fn shallow() {}
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+// Fixture: annotations of comments and attributes preceding an item, in source order.
+
+/// lobster-trace: SWRS.first
+#[lobster::trace(SWRS.second, SWRS.third)]
+// lobster-trace: SWRS.fourth
+#[doc = "lobster-trace: SWRS.fifth"]
+fn mixed() {}
+
+struct Config {
+    #[doc = "lobster-trace: SWRS.attr_first"]
+    /// lobster-trace: SWRS.comment_second
+    #[lobster::trace(SWRS.attr_third)]
+    value: u8,
+}
+
+// lobster-trace: SWRS.detached
+
+fn separated() {}
+
+fn main() {}
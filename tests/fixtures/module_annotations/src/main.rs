@@ -0,0 +1,15 @@
+// Fixture: annotated inline and file modules.
+
+/// lobster-trace: SWRS.storage
+mod storage;
+
+// lobster-trace: SWRS.inline
+mod inline {
+    fn helper() {}
+}
+
+mod plain {
+    fn other() {}
+}
+
+fn main() {}
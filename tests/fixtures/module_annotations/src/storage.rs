@@ -0,0 +1,3 @@
+// Fixture: module annotated at its declaration.
+
+pub fn store() {}
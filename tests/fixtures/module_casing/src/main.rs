@@ -0,0 +1,5 @@
+// Fixture: module declaration whose file name differs in casing.
+
+mod Storage;
+
+fn main() {}
@@ -0,0 +1,3 @@
+// Module resolved case-insensitively.
+
+pub fn save() {}
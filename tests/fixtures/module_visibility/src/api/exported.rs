@@ -0,0 +1,3 @@
+// Module resolved via a path attribute before the visibility.
+
+pub fn exported_fn() {}
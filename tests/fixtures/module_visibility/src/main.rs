@@ -0,0 +1,24 @@
+// Fixture: module declarations with visibility and attributes.
+
+pub mod public;
+
+pub(crate) mod restricted;
+
+#[path = "api/exported.rs"]
+pub mod exported;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg( test )]
+pub(crate) mod test_support;
+
+#[cfg(feature = "extra")]
+pub mod extra;
+
+#[cfg(test)]
+pub mod inline_tests {
+    fn check() {}
+}
+
+fn main() {}
@@ -0,0 +1,3 @@
+// Module declared with visibility.
+
+pub fn public_fn() {}
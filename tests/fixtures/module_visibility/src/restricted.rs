@@ -0,0 +1,3 @@
+// Module declared with visibility.
+
+pub fn restricted_fn() {}
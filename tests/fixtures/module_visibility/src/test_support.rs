@@ -0,0 +1,3 @@
+// Test support module without an inner cfg attribute.
+
+pub fn support() {}
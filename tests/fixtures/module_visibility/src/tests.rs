@@ -0,0 +1,3 @@
+// Test module without an inner cfg attribute.
+
+fn test_main() {}
@@ -0,0 +1,5 @@
+// File included via the include! macro.
+
+fn generated() {
+    // lobster-trace: example.generated
+}
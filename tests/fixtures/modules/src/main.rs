@@ -0,0 +1,9 @@
+// Fixture: module declarations and included files.
+
+mod sub;
+
+include!("generated.rs");
+
+fn main() {
+    // lobster-trace: example.main
+}
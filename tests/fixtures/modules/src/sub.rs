@@ -0,0 +1,5 @@
+// Submodule resolved via a module declaration.
+
+pub fn helper() {
+    // lobster-trace: example.helper
+}
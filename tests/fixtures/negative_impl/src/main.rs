@@ -0,0 +1,7 @@
+// Fixture: negative impl without any items.
+
+struct Handle;
+
+impl !Send for Handle {}
+
+fn main() {}
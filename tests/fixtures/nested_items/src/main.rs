@@ -0,0 +1,18 @@
+// Fixture: annotations in function bodies and in nested items.
+
+fn outer() {
+    // lobster-trace: example.outer
+
+    struct Inner {
+        // lobster-trace: example.inner
+        value: u8,
+    }
+
+    fn helper() {
+        // lobster-trace: example.helper
+    }
+
+    // lobster-trace: example.outer_after
+}
+
+fn main() {}
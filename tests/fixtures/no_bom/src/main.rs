@@ -0,0 +1,5 @@
+fn first() {
+    // lobster-trace: example.first
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+// Fixture: items with the same name in different namespaces, named via --no-context.
+
+mod sub;
+
+struct Foo;
+
+impl Foo {
+    // lobster-trace: SWRS.method
+    fn helper(&self) {}
+}
+
+// lobster-trace: SWRS.helper
+fn helper() {}
+
+fn main() {}
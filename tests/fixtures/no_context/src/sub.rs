@@ -0,0 +1,4 @@
+// lobster-trace: SWRS.sub_helper
+fn helper() {}
+
+fn unique() {}
@@ -0,0 +1,19 @@
+// Fixture: non-exhaustive structs and enums.
+
+// lobster-trace: SWRS.options
+#[non_exhaustive]
+pub struct Options {
+    pub verbose: bool,
+}
+
+// lobster-trace: SWRS.mode
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Mode {
+    Fast,
+    Safe,
+}
+
+struct Plain;
+
+fn main() {}
@@ -0,0 +1,4 @@
+// Fixture: module shared via a path attribute, located next to src.
+
+// lobster-trace: SWRS.shared
+fn shared() {}
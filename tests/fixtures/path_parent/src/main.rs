@@ -0,0 +1,9 @@
+// Fixture: path attributes with `..` components.
+
+#[path = "../shared/common.rs"]
+mod common;
+
+#[path = "../shared/missing.rs"]
+mod missing;
+
+fn main() {}
@@ -0,0 +1,20 @@
+// Fixture: public re-exports with single and braced paths.
+
+mod shapes {
+    pub struct Circle;
+    pub struct Square;
+    pub struct Triangle;
+    pub fn area() {}
+}
+
+/// lobster-trace: SWRS.circle
+pub use shapes::Circle;
+
+/// lobster-trace: SWRS.polygons
+pub use shapes::{Square, Triangle as Tri};
+
+pub(crate) use shapes::area;
+
+pub use shapes::*;
+
+fn main() {}
@@ -0,0 +1,14 @@
+fn software() {
+    // lobster-trace: SWRS.login
+}
+
+fn system() {
+    // lobster-trace: SYRS.availability
+}
+
+fn both() {
+    // lobster-trace: SWRS.logout
+    // lobster-trace: SYRS.security
+}
+
+fn main() {}
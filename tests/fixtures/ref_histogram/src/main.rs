@@ -0,0 +1,15 @@
+// Fixture: requirements referenced by several items, counted via --ref-histogram.
+
+// lobster-trace: SWRS.common
+// lobster-trace: SWRS.single
+fn first() {}
+
+fn second() {
+    // lobster-trace: SWRS.common
+    // lobster-trace: SWRS.common
+}
+
+// lobster-trace: SWRS.common
+struct Third;
+
+fn main() {}
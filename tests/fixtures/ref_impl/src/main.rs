@@ -0,0 +1,29 @@
+// Fixture: impl blocks for references are named after the reference type.
+
+use std::fmt;
+
+struct Foo;
+
+impl fmt::Display for Foo {
+    // lobster-trace: SWRS.display
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl fmt::Display for &Foo {
+    // lobster-trace: SWRS.display_ref
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for &'a mut Foo {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        None
+    }
+}
+
+fn main() {}
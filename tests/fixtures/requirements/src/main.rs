@@ -0,0 +1,9 @@
+fn known() {
+    // lobster-trace: SWRS.known
+}
+
+fn dangling() {
+    // lobster-trace: SWRS.missing
+}
+
+fn main() {}
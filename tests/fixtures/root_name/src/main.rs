@@ -0,0 +1,11 @@
+// Fixture: top-level context overridden via --root-name.
+
+mod sub;
+
+mod inner {
+    fn nested() {}
+}
+
+fn main() {
+    // lobster-trace: example.root
+}
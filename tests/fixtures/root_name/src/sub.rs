@@ -0,0 +1,3 @@
+// Fixture: submodule below the overridden top-level context.
+
+pub fn helper() {}
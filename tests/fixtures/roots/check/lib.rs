@@ -0,0 +1,3 @@
+// Checker library, not described by Cargo.
+
+pub fn check() {}
@@ -0,0 +1,5 @@
+// Generator entry point, not described by Cargo.
+
+mod util;
+
+fn generate() {}
@@ -0,0 +1,3 @@
+// Module of the generator.
+
+pub fn helper() {}
@@ -0,0 +1,3 @@
+// Fixture: crate roots listed in a roots manifest.
+
+fn main() {}
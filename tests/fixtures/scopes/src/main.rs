@@ -0,0 +1,15 @@
+// Fixture: items nested in modules, impl blocks and functions.
+
+mod outer {
+    mod inner {
+        struct Item;
+
+        impl Item {
+            fn method() {
+                fn local() {}
+            }
+        }
+    }
+}
+
+fn main() {}
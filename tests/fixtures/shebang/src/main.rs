@@ -0,0 +1,4 @@
+#!/usr/bin/env rust-script
+#![allow(dead_code)] fn first() {}
+#[inline] fn second() {}
+fn main() {}
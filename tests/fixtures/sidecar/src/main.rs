@@ -0,0 +1,9 @@
+// Fixture: references attached from a sidecar file.
+
+mod sub;
+
+struct Config;
+
+fn main() {
+    // lobster-trace: example.comment
+}
@@ -0,0 +1,3 @@
+// Fixture: submodule with references from a sidecar file.
+
+pub fn helper() {}
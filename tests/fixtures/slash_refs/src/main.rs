@@ -0,0 +1,15 @@
+// Fixture: requirement references and justifications containing slashes.
+
+fn jira() {
+    // lobster-trace: PROJ/123
+}
+
+fn nested() {
+    // lobster-trace: REQ/SW/4
+    // lobster-exclude-down: TEST/HW/1
+}
+
+/* lobster-trace: REQ/SW/5 */
+fn block() {}
+
+fn main() {}
@@ -0,0 +1,7 @@
+// Fixture: two source files written to separate output files.
+
+mod sub;
+
+fn main() {
+    // lobster-trace: example.main
+}
@@ -0,0 +1,5 @@
+// Fixture: submodule written to its own output file.
+
+pub fn helper() {
+    // lobster-trace: example.helper
+}
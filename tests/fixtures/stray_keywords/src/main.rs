@@ -0,0 +1,17 @@
+// Fixture: stray keywords the parser recovers from must not abort the parsing.
+
+struct Broken {
+    fn
+    struct
+}
+
+impl Broken {
+    fn
+}
+
+fn stray() {
+    let pointer = fn;
+    let value = struct;
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+// Fixture: references with a project prefix, stripped via --strip-prefix CompanyProj.
+
+// lobster-trace: CompanyProj.SW.REQ.1
+fn prefixed() {}
+
+// lobster-trace: Other.SW.REQ.2
+fn unprefixed() {}
+
+// lobster-trace(design): CompanyProj.DESIGN.3
+fn with_kind() {}
+
+fn main() {}
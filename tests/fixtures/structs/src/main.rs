@@ -0,0 +1,17 @@
+// Fixture: named, tuple and unit structs.
+
+struct Named {
+    // lobster-trace: example.named
+    field: u8,
+}
+
+struct Tuple(
+    // lobster-trace: example.tuple
+    u8,
+    u8,
+);
+
+/// lobster-trace: example.unit
+struct Unit;
+
+fn main() {}
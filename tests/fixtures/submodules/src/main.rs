@@ -0,0 +1,5 @@
+// Fixture: consistent prefixes of items in the entry file and in submodules.
+
+mod sub;
+
+fn main() {}
@@ -0,0 +1,5 @@
+// Fixture: submodule declared in the entry file.
+
+mod nested;
+
+pub fn helper() {}
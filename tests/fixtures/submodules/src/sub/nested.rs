@@ -0,0 +1,3 @@
+// Fixture: submodule declared in a submodule.
+
+pub fn deep() {}
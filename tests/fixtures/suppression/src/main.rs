@@ -0,0 +1,11 @@
+// Fixture: suppression of the coverage gate.
+
+fn excluded() {
+    // lobster-exclude: UNTRACED
+}
+
+fn unchecked() {
+    // lobster-nocheck
+}
+
+fn untraced() {}
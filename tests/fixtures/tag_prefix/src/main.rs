@@ -0,0 +1,10 @@
+// Fixture: items tagged with a custom tag prefix.
+
+struct Counter;
+
+impl Counter {
+    // lobster-trace: SWRS.increment
+    fn increment(&mut self) {}
+}
+
+fn main() {}
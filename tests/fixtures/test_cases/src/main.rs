@@ -0,0 +1,16 @@
+// Fixture: parameterized tests with test_case attributes.
+
+#[cfg(test)]
+mod tests {
+    #[test_case(1, 2 ; "small")]
+    #[test_case(10, 20 ; "large")]
+    #[test_case(0, 0)]
+    fn adds(a: u8, b: u8) {
+        // lobster-trace: SWRS.add
+    }
+
+    #[test_case(1)]
+    fn single(value: u8) {}
+}
+
+fn main() {}
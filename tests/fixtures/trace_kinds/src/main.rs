@@ -0,0 +1,11 @@
+// Fixture: trace annotations to requirements of different kinds.
+
+fn connect() {
+    // lobster-trace: SWRS.connect
+    // lobster-trace(design): DESIGN.connection
+}
+
+/// lobster-trace(design): DESIGN.storage
+struct Storage;
+
+fn main() {}
@@ -0,0 +1,20 @@
+trait Greet {
+    /// lobster-trace: SWRS.greet
+    fn greet(&self);
+
+    fn wave(&self) {
+        // lobster-trace: SWRS.wave
+    }
+}
+
+struct Person;
+
+impl Greet for Person {
+    fn greet(&self) {
+        // lobster-trace: SWRS.greet_person
+    }
+
+    fn wave(&self) {}
+}
+
+fn main() {}
@@ -0,0 +1,4 @@
+pub trait Shape {
+    /// lobster-trace: SWRS.drawing_outline
+    fn outline(&self);
+}
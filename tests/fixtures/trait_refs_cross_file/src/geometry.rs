@@ -0,0 +1,4 @@
+pub trait Shape {
+    /// lobster-trace: SWRS.geometry_outline
+    fn outline(&self);
+}
@@ -0,0 +1,29 @@
+// Fixture: trait methods inherit references across files, matched by the path of the trait.
+
+mod drawing;
+mod geometry;
+
+use geometry::Shape;
+
+struct Square;
+
+struct Circle;
+
+impl Local for Square {
+    fn local(&self) {}
+}
+
+impl Shape for Square {
+    fn outline(&self) {}
+}
+
+impl drawing::Shape for Circle {
+    fn outline(&self) {}
+}
+
+trait Local {
+    /// lobster-trace: SWRS.local
+    fn local(&self);
+}
+
+fn main() {}
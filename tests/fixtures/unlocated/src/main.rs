@@ -0,0 +1,9 @@
+// Fixture: traced and untraced functions whose location is dropped while parsing.
+
+fn lost() {
+    // lobster-trace: SWRS.lost
+}
+
+fn main() {
+    lost();
+}
@@ -0,0 +1,11 @@
+// Fixture: module without a name followed by regular items.
+
+mod {
+    fn orphan() {}
+}
+
+mod named {
+    fn inner() {}
+}
+
+fn main() {}
@@ -0,0 +1,5 @@
+// Fixture: module declaration without a corresponding file.
+
+mod missing;
+
+fn main() {}
@@ -0,0 +1,13 @@
+struct Handle;
+
+unsafe impl Send for Handle {}
+
+unsafe trait Raw {
+    fn raw(&self);
+}
+
+unsafe impl Raw for Handle {
+    fn raw(&self) {}
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+// Fixture: coverage gate for untraced items.
+
+struct Traced {
+    // lobster-trace: example.struct
+    value: u8,
+}
+
+fn traced() {
+    // lobster-trace: example.traced
+}
+
+fn justified() {
+    // lobster-exclude: Not_required
+}
+
+fn untraced() {}
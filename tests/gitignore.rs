@@ -0,0 +1,81 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Tests for the matching of .gitignore patterns.
+
+use lobster_rust::gitignore::Gitignore;
+use std::path::Path;
+
+/// Check if a path relative to the repository root is ignored by the given .gitignore content.
+fn is_ignored(content: &str, path: &str) -> bool {
+    let base_dir = Path::new("/repo");
+    Gitignore::parse(content, base_dir).is_ignored(&base_dir.join(path))
+}
+
+#[test]
+fn unanchored_pattern_matches_at_any_depth() {
+    assert!(is_ignored("scratch.rs", "scratch.rs"));
+    assert!(is_ignored("scratch.rs", "src/bin/scratch.rs"));
+    assert!(!is_ignored("scratch.rs", "src/scratch.rs.bak"));
+}
+
+#[test]
+fn anchored_pattern_matches_relative_to_gitignore() {
+    assert!(is_ignored("/src/generated.rs", "src/generated.rs"));
+    assert!(!is_ignored("/src/generated.rs", "sub/src/generated.rs"));
+    assert!(is_ignored("src/*.rs", "src/generated.rs"));
+    assert!(!is_ignored("src/*.rs", "src/nested/generated.rs"));
+}
+
+#[test]
+fn wildcards_are_supported() {
+    assert!(is_ignored("gen_?.rs", "src/gen_a.rs"));
+    assert!(is_ignored("gen_[ab].rs", "src/gen_b.rs"));
+    assert!(!is_ignored("gen_[!ab].rs", "src/gen_b.rs"));
+    assert!(is_ignored("**/out/*.rs", "a/b/out/gen.rs"));
+    assert!(is_ignored("src/**", "src/a/b.rs"));
+}
+
+#[test]
+fn ignored_directory_ignores_its_contents() {
+    assert!(is_ignored("target/", "target/debug/build.rs"));
+    assert!(!is_ignored("target/", "target"));
+}
+
+#[test]
+fn negated_pattern_reincludes_files() {
+    let content = "# Generated code\n*.rs\n!main.rs\n";
+    assert!(is_ignored(content, "src/generated.rs"));
+    assert!(!is_ignored(content, "src/main.rs"));
+}
+
+#[test]
+fn paths_outside_the_repository_are_not_ignored() {
+    assert!(!is_ignored("*.rs", "../other/main.rs"));
+}
@@ -0,0 +1,1582 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! End-to-end golden file tests for the lobster common interchange format output.
+//!
+//! Every case is a fixture crate under `tests/fixtures/<case>/src`. The lobster-rust binary is run
+//! on the fixture and its output is compared against the committed
+//! `tests/fixtures/<case>/expected.lobster`.
+
+use json::{object::Object, JsonValue};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Counter of lobster-rust runs, used to name the output files.
+static RUN_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Placeholder replacing the absolute fixture directory in the output.
+const FIXTURE_PLACEHOLDER: &str = "$FIXTURE";
+
+/// Normalize a lobster output for comparison.
+///
+/// Replaces the absolute path of the fixture directory in all strings by a placeholder and
+/// converts backslashes to forward slashes, so that the output is independent of the machine the
+/// test is run on.
+///
+/// ### Parameters
+/// * `value` - JsonValue to normalize.
+/// * `fixture_dir` - Absolute path of the fixture directory.
+///
+/// ### Returns
+/// The normalized JsonValue.
+fn normalize(value: &JsonValue, fixture_dir: &Path) -> JsonValue {
+    match value {
+        JsonValue::Short(_) | JsonValue::String(_) => {
+            let fixture_path = fixture_dir.display().to_string();
+            let text = value.as_str().unwrap_or_default();
+            JsonValue::String(
+                text.replace(&fixture_path, FIXTURE_PLACEHOLDER)
+                    .replace('\\', "/"),
+            )
+        }
+        JsonValue::Array(items) => {
+            JsonValue::Array(items.iter().map(|i| normalize(i, fixture_dir)).collect())
+        }
+        JsonValue::Object(object) => {
+            let mut normalized = Object::new();
+            for (key, item) in object.iter() {
+                normalized.insert(key, normalize(item, fixture_dir));
+            }
+            JsonValue::Object(normalized)
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Get the absolute path of a fixture directory.
+fn fixture_dir(case: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(case)
+}
+
+/// Run lobster-rust on a fixture.
+///
+/// ### Parameters
+/// * `case` - Name of the fixture directory.
+/// * `extra_args` - Additional command line arguments.
+///
+/// ### Returns
+/// The output of the lobster-rust process and the path of the written lobster file.
+fn run_case(case: &str, extra_args: &[&str]) -> (Output, PathBuf) {
+    // Every run writes to its own file, as tests run in parallel.
+    let run = RUN_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let outfile = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("{case}_{run}.lobster"));
+    let output = Command::new(env!("CARGO_BIN_EXE_lobster-rust"))
+        .arg(fixture_dir(case).join("src"))
+        .arg(&outfile)
+        .args(extra_args)
+        .output()
+        .expect("failed to run lobster-rust");
+    (output, outfile)
+}
+
+/// Run lobster-rust on a fixture and compare the output with the expected output.
+///
+/// ### Parameters
+/// * `case` - Name of the fixture directory.
+/// * `extra_args` - Additional command line arguments.
+fn check_case(case: &str, extra_args: &[&str]) {
+    let fixture_dir = fixture_dir(case);
+    let (output, outfile) = run_case(case, extra_args);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let actual = normalize(&actual, &fixture_dir);
+    let expected =
+        json::parse(&fs::read_to_string(fixture_dir.join("expected.lobster")).unwrap()).unwrap();
+    assert_eq!(
+        actual,
+        expected,
+        "output of case {case} differs from expected.lobster:\n{}",
+        actual.pretty(4)
+    );
+}
+
+#[test]
+fn golden_functions() {
+    check_case("functions", &[]);
+}
+
+#[test]
+fn golden_exported_symbols() {
+    check_case("exported_symbols", &[]);
+}
+
+#[test]
+fn dash_writes_output_to_stdout() {
+    let fixture_dir = fixture_dir("functions");
+    let output = Command::new(env!("CARGO_BIN_EXE_lobster-rust"))
+        .arg(fixture_dir.join("src"))
+        .arg("-")
+        .output()
+        .expect("failed to run lobster-rust");
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    let actual = json::parse(&String::from_utf8_lossy(&output.stdout)).unwrap();
+    let actual = normalize(&actual, &fixture_dir);
+    let expected =
+        json::parse(&fs::read_to_string(fixture_dir.join("expected.lobster")).unwrap()).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn file_as_dir_is_parsed_as_entry_file() {
+    let fixture_dir = fixture_dir("functions");
+    let run = RUN_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let outfile =
+        PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("file_dir_{run}.lobster"));
+    let output = Command::new(env!("CARGO_BIN_EXE_lobster-rust"))
+        .arg(fixture_dir.join("src/main.rs"))
+        .arg(&outfile)
+        .output()
+        .expect("failed to run lobster-rust");
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let actual = normalize(&actual, &fixture_dir);
+    let expected =
+        json::parse(&fs::read_to_string(fixture_dir.join("expected.lobster")).unwrap()).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn print_schema_describes_the_output() {
+    let output = Command::new(env!("CARGO_BIN_EXE_lobster-rust"))
+        .arg("--print-schema")
+        .output()
+        .expect("failed to run lobster-rust");
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let schema = json::parse(&String::from_utf8_lossy(&output.stdout)).unwrap();
+    assert_eq!(schema["properties"]["schema"]["const"], "lobster-imp-trace");
+
+    // Every item of an output has the required fields and only declared fields.
+    let (output, outfile) = run_case("functions", &[]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let item_schema = &schema["properties"]["data"]["items"];
+    for item in actual["data"].members() {
+        for required in item_schema["required"].members() {
+            assert!(
+                item.has_key(required.as_str().unwrap()),
+                "{required} in {item}"
+            );
+        }
+        for (key, _) in item.entries() {
+            assert!(item_schema["properties"].has_key(key), "{key} in {item}");
+        }
+    }
+}
+
+#[test]
+fn schema_ref_is_only_emitted_on_request() {
+    let url = "https://example.com/lobster-imp-trace.json";
+    let (output, outfile) = run_case("functions", &["--schema-ref", url]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    assert_eq!(actual["$schema"], url);
+
+    let (output, outfile) = run_case("functions", &[]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    assert!(!actual.has_key("$schema"));
+}
+
+#[test]
+fn version_info_prints_parser_details() {
+    let lines = version_info(&[]);
+    assert_eq!(
+        lines,
+        [
+            format!("lobster-rust: {}", env!("CARGO_PKG_VERSION")),
+            format!("ra_ap_syntax: {}", locked_version("ra_ap_syntax")),
+            "edition: 2024".to_string(),
+            "schema: lobster-imp-trace".to_string(),
+            "schema_version: 3".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn parser_version_matches_the_manifest() {
+    let lines = version_info(&[]);
+    assert_eq!(
+        lines[1],
+        format!("ra_ap_syntax: {}", manifest_requirement("ra_ap_syntax"))
+    );
+}
+
+#[test]
+fn version_info_follows_activity_mode() {
+    let lines = version_info(&["--activity"]);
+    assert_eq!(
+        lines[3..],
+        ["schema: lobster-act-trace", "schema_version: 3"]
+    );
+}
+
+/// Run lobster-rust with --version-info.
+///
+/// ### Parameters
+/// * `extra_args` - Additional command line arguments.
+///
+/// ### Returns
+/// The printed lines.
+fn version_info(extra_args: &[&str]) -> Vec<String> {
+    let output = Command::new(env!("CARGO_BIN_EXE_lobster-rust"))
+        .arg("--version-info")
+        .args(extra_args)
+        .output()
+        .expect("failed to run lobster-rust");
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Get the version of a package resolved in the Cargo.lock of lobster-rust.
+///
+/// ### Parameters
+/// * `package` - Name of the package.
+///
+/// ### Returns
+/// The locked version of the package.
+fn locked_version(package: &str) -> String {
+    let lock =
+        fs::read_to_string(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.lock")).unwrap();
+    let name_line = format!("name = \"{package}\"");
+    let version_line = lock
+        .lines()
+        .skip_while(|line| *line != name_line)
+        .nth(1)
+        .unwrap();
+    version_line
+        .trim_start_matches("version = ")
+        .trim_matches('"')
+        .to_string()
+}
+
+/// Get the version requirement of a dependency in the Cargo.toml of lobster-rust.
+///
+/// ### Parameters
+/// * `package` - Name of the dependency.
+///
+/// ### Returns
+/// The required version of the dependency.
+fn manifest_requirement(package: &str) -> String {
+    let manifest =
+        fs::read_to_string(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml")).unwrap();
+    let prefix = format!("{package} = ");
+    manifest
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix))
+        .unwrap()
+        .trim_matches('"')
+        .to_string()
+}
+
+#[test]
+fn skip_kinds_leaves_out_structs_but_keeps_methods() {
+    let fixture_dir = fixture_dir("impls");
+    let (output, outfile) = run_case("impls", &["--skip-kinds", "struct"]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let actual = normalize(&actual, &fixture_dir);
+    let mut expected =
+        json::parse(&fs::read_to_string(fixture_dir.join("expected.lobster")).unwrap()).unwrap();
+    let mut items: Vec<JsonValue> = expected["data"]
+        .members()
+        .filter(|item| item["kind"] != "Struct")
+        .cloned()
+        .collect();
+    // Methods of the skipped structs have no emitted parent left.
+    let tags: Vec<JsonValue> = items.iter().map(|item| item["tag"].clone()).collect();
+    for item in items.iter_mut() {
+        if !item["parent_tag"].is_null() && !tags.contains(&item["parent_tag"]) {
+            item["parent_tag"] = JsonValue::Null;
+        }
+    }
+    expected["data"] = JsonValue::Array(items);
+    assert_eq!(actual, expected);
+    assert!(actual["data"]
+        .members()
+        .any(|item| item["name"] == "main.Wrapper.value"));
+}
+
+#[test]
+fn skip_kinds_repoints_parent_tags() {
+    let parent_tags = |case: &str, kind: &str| {
+        let (output, outfile) = run_case(case, &["--skip-kinds", kind]);
+        assert!(output.status.success(), "lobster-rust failed: {output:?}");
+        let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+        actual["data"]
+            .members()
+            .map(|item| (item["name"].to_string(), item["parent_tag"].clone()))
+            .collect::<Vec<_>>()
+    };
+
+    // Fields of a skipped struct have no emitted parent.
+    let fields = parent_tags("fields", "struct");
+    assert!(fields.contains(&("main.Config.timeout".to_string(), JsonValue::Null)));
+
+    // Structs nested in the body of a skipped function have no emitted parent either.
+    let local_items = parent_tags("local_items", "function");
+    assert_eq!(
+        local_items,
+        [
+            ("main.first.Local".to_string(), JsonValue::Null),
+            ("main.second.Local".to_string(), JsonValue::Null),
+        ]
+    );
+}
+
+#[test]
+fn golden_slash_refs() {
+    check_case("slash_refs", &[]);
+}
+
+#[test]
+fn golden_impls() {
+    check_case("impls", &[]);
+}
+
+#[test]
+fn golden_impl_types() {
+    check_case("impl_types", &[]);
+}
+
+#[test]
+fn golden_trait_refs() {
+    check_case("trait_refs", &[]);
+}
+
+#[test]
+fn golden_trait_refs_cross_file() {
+    check_case("trait_refs_cross_file", &[]);
+}
+
+#[test]
+fn golden_generic_bounds() {
+    check_case("generic_bounds", &[]);
+}
+
+#[test]
+fn golden_unsafe_impl() {
+    check_case("unsafe_impl", &[]);
+}
+
+#[test]
+fn golden_lifetime_impl() {
+    check_case("lifetime_impl", &[]);
+}
+
+#[test]
+fn golden_negative_impl() {
+    check_case("negative_impl", &[]);
+}
+
+#[test]
+fn negative_impl_does_not_warn() {
+    let (output, _) = run_case("negative_impl", &[]);
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("WARNING"));
+}
+
+#[test]
+fn golden_blanket_impl() {
+    check_case("blanket_impl", &[]);
+}
+
+#[test]
+fn golden_structs() {
+    check_case("structs", &[]);
+}
+
+#[test]
+fn tuple_and_unit_structs_are_traced() {
+    let (output, outfile) = run_case("structs", &[]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let structs: Vec<(&str, &str)> = actual["data"]
+        .members()
+        .filter(|item| item["kind"] == "Struct")
+        .map(|item| {
+            (
+                item["name"].as_str().unwrap_or_default(),
+                item["refs"][0].as_str().unwrap_or_default(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        structs,
+        [
+            ("main.Named", "req example.named"),
+            ("main.Tuple", "req example.tuple"),
+            ("main.Unit", "req example.unit"),
+        ]
+    );
+}
+
+#[test]
+fn golden_fields() {
+    check_case("fields", &[]);
+}
+
+#[test]
+fn golden_complexity() {
+    check_case("complexity", &["--complexity"]);
+}
+
+#[test]
+fn golden_mixed_annotations() {
+    check_case("mixed_annotations", &[]);
+}
+
+#[test]
+fn golden_error_enum() {
+    check_case("error_enum", &["--trace-enums"]);
+}
+
+#[test]
+fn enums_are_only_traced_on_request() {
+    let (output, outfile) = run_case("error_enum", &[]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let tags: Vec<&str> = actual["data"]
+        .members()
+        .filter_map(|item| item["tag"].as_str())
+        .collect();
+    assert_eq!(tags, ["rust main.main"]);
+}
+
+#[test]
+fn golden_non_exhaustive() {
+    check_case("non_exhaustive", &["--trace-enums"]);
+}
+
+#[test]
+fn golden_doc_attributes() {
+    check_case("doc_attributes", &[]);
+}
+
+#[test]
+fn golden_modules() {
+    check_case("modules", &[]);
+}
+
+#[test]
+fn golden_include_literals() {
+    check_case("include_literals", &[]);
+}
+
+#[test]
+fn golden_module_annotations() {
+    check_case("module_annotations", &[]);
+}
+
+#[test]
+fn golden_fn_modifiers() {
+    check_case("fn_modifiers", &[]);
+}
+
+#[test]
+fn golden_const_fn() {
+    check_case("const_fn", &[]);
+}
+
+#[test]
+fn golden_root_name() {
+    check_case("root_name", &["--root-name", "mycrate"]);
+}
+
+#[test]
+fn golden_tag_prefix() {
+    check_case("tag_prefix", &["--tag-prefix", "mycrate:"]);
+}
+
+#[test]
+fn golden_submodules() {
+    check_case("submodules", &[]);
+}
+
+#[test]
+fn golden_max_context_depth() {
+    check_case("max_context_depth", &["--max-context-depth", "2"]);
+}
+
+#[test]
+fn golden_ref_filter() {
+    check_case("ref_filter", &["--ref-filter", "SWRS."]);
+}
+
+#[test]
+fn split_output_writes_one_file_per_source_file() {
+    let fixture_dir = fixture_dir("split_output");
+    let split_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("split_output");
+    let _ = fs::remove_dir_all(&split_dir);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_lobster-rust"))
+        .arg(fixture_dir.join("src"))
+        .arg("--split-output")
+        .arg(&split_dir)
+        .output()
+        .expect("failed to run lobster-rust");
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    let mut filenames: Vec<String> = fs::read_dir(&split_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    filenames.sort();
+    assert_eq!(filenames, ["main.lobster", "sub.lobster"]);
+
+    for (filename, item_name) in [
+        ("main.lobster", "main.main"),
+        ("sub.lobster", "main.sub.helper"),
+    ] {
+        let document = json::parse(&fs::read_to_string(split_dir.join(filename)).unwrap()).unwrap();
+        assert_eq!(document["generator"], "lobster-rust");
+        assert_eq!(document["schema"], "lobster-imp-trace");
+        assert_eq!(document["version"], 3);
+        assert_eq!(document["data"].len(), 1);
+        assert_eq!(document["data"][0]["name"], item_name);
+    }
+}
+
+#[test]
+fn golden_empty() {
+    check_case("empty", &[]);
+}
+
+#[test]
+fn golden_comment_only() {
+    check_case("comment_only", &[]);
+}
+
+#[test]
+fn golden_sidecar() {
+    let sidecar = fixture_dir("sidecar").join("trace.tsv");
+    check_case("sidecar", &["--sidecar", sidecar.to_str().unwrap()]);
+}
+
+#[test]
+fn golden_macro_invocations() {
+    check_case("macro_invocations", &[]);
+}
+
+#[test]
+fn golden_macro_def() {
+    check_case("macro_def", &[]);
+}
+
+#[test]
+fn golden_re_exports() {
+    check_case("re_exports", &[]);
+}
+
+#[test]
+fn golden_inner_doc() {
+    check_case("inner_doc", &[]);
+}
+
+#[test]
+fn golden_cfg_impl() {
+    check_case("cfg_impl", &[]);
+}
+
+#[test]
+fn golden_directional_justifications() {
+    check_case("directional_justifications", &[]);
+}
+
+#[test]
+fn golden_justification_reasons() {
+    check_case("justification_reasons", &[]);
+}
+
+#[test]
+fn golden_trace_kinds() {
+    check_case("trace_kinds", &[]);
+}
+
+#[test]
+fn fail_on_untraced_reports_untraced_functions() {
+    let (output, outfile) = run_case("untraced", &["--fail-on-untraced"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Untraced Function main.untraced in main @16,1"));
+    assert!(!stderr.contains("main.traced"));
+    assert!(!stderr.contains("main.justified"));
+    assert!(outfile.exists());
+}
+
+#[test]
+fn fail_on_untraced_passes_if_required_kinds_are_traced() {
+    let (output, _) = run_case(
+        "untraced",
+        &["--fail-on-untraced", "--require-trace-on", "struct"],
+    );
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "lobster-rust failed: {output:?}"
+    );
+}
+
+#[test]
+fn junit_reports_untraced_items_as_failures() {
+    let run = RUN_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let junit = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("junit_{run}.xml"));
+    let (output, _) = run_case("untraced", &["--junit", junit.to_str().unwrap()]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    let report = fs::read_to_string(junit).unwrap();
+    let document = roxmltree::Document::parse(&report).unwrap();
+    let suites = document.root_element();
+    assert_eq!(suites.tag_name().name(), "testsuites");
+    let suite = suites.first_element_child().unwrap();
+    assert_eq!(suite.tag_name().name(), "testsuite");
+    assert_eq!(suite.attribute("name"), Some("lobster-rust"));
+    assert_eq!(suite.attribute("tests"), Some("3"));
+    assert_eq!(suite.attribute("failures"), Some("1"));
+
+    let cases: Vec<roxmltree::Node> = suite.children().filter(|n| n.is_element()).collect();
+    let attributes: Vec<[Option<&str>; 4]> = cases
+        .iter()
+        .map(|case| {
+            ["classname", "name", "file", "line"].map(|attribute| case.attribute(attribute))
+        })
+        .collect();
+    assert_eq!(
+        attributes,
+        [
+            [Some("main"), Some("traced"), Some("src/main.rs"), Some("8")],
+            [
+                Some("main"),
+                Some("justified"),
+                Some("src/main.rs"),
+                Some("12")
+            ],
+            [
+                Some("main"),
+                Some("untraced"),
+                Some("src/main.rs"),
+                Some("16")
+            ],
+        ]
+    );
+    let failures: Vec<Option<&str>> = cases
+        .iter()
+        .flat_map(|case| case.children().filter(|n| n.has_tag_name("failure")))
+        .map(|failure| failure.attribute("message"))
+        .collect();
+    assert_eq!(
+        failures,
+        [Some("Untraced Function main.untraced in main @16,1")]
+    );
+}
+
+#[test]
+fn fail_on_untraced_respects_suppressions() {
+    let (output, _) = run_case("suppression", &["--fail-on-untraced"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("main.untraced"));
+    assert!(!stderr.contains("main.excluded"));
+    assert!(!stderr.contains("main.unchecked"));
+}
+
+#[test]
+fn golden_columns() {
+    check_case("columns", &[]);
+}
+
+#[test]
+fn golden_dense() {
+    check_case("dense", &[]);
+}
+
+#[test]
+fn golden_shebang() {
+    check_case("shebang", &[]);
+}
+
+#[test]
+fn deeply_nested_expressions_do_not_overflow_the_stack() {
+    let (output, outfile) = run_case("deep_nesting", &[]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let deep = actual["data"]
+        .members()
+        .find(|item| item["name"] == "main.deep")
+        .expect("main.deep not found");
+    assert_eq!(deep["refs"][0], "req SWRS.deep");
+}
+
+#[test]
+fn golden_cfg_test() {
+    check_case("cfg_test", &[]);
+}
+
+#[test]
+fn golden_module_visibility() {
+    check_case("module_visibility", &["--cfg-filter"]);
+}
+
+#[test]
+fn cfg_test_modules_are_only_skipped_on_request() {
+    let (output, outfile) = run_case("module_visibility", &[]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let names: Vec<&str> = actual["data"]
+        .members()
+        .filter_map(|item| item["name"].as_str())
+        .collect();
+    assert!(names.contains(&"main.tests.test_main"));
+    assert!(names.contains(&"main.test_support.support"));
+    assert!(names.contains(&"main.inline_tests.check"));
+}
+
+#[test]
+fn zero_based_columns() {
+    let (output, outfile) = run_case("columns", &["--zero-based-columns"]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let columns: Vec<Option<usize>> = actual["data"]
+        .members()
+        .map(|item| item["location"]["column"].as_usize())
+        .collect();
+    assert_eq!(columns, [Some(0), Some(4), Some(0)]);
+}
+
+#[test]
+fn append_merges_items_by_tag() {
+    let run = RUN_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let appended = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("append_{run}.lobster"));
+    // The target directory outlives the test run, start without document.
+    let _ = fs::remove_file(&appended);
+    let append = |case: &str| {
+        Command::new(env!("CARGO_BIN_EXE_lobster-rust"))
+            .arg(fixture_dir(case).join("src"))
+            .args(["--append", appended.to_str().unwrap()])
+            .output()
+            .expect("failed to run lobster-rust")
+    };
+
+    let first = append("functions");
+    assert!(first.status.success(), "lobster-rust failed: {first:?}");
+    let second = append("modules");
+    assert!(second.status.success(), "lobster-rust failed: {second:?}");
+    let stderr = String::from_utf8_lossy(&second.stderr);
+    assert!(
+        stderr.contains("WARNING: Replacing rust main.main at main:"),
+        "{stderr}"
+    );
+    // Appending the same items again replaces them without conflicts.
+    let third = append("modules");
+    assert!(third.status.success(), "lobster-rust failed: {third:?}");
+    assert!(!String::from_utf8_lossy(&third.stderr).contains("WARNING"));
+
+    let merged = json::parse(&fs::read_to_string(&appended).unwrap()).unwrap();
+    let tags: Vec<&str> = merged["data"]
+        .members()
+        .filter_map(|item| item["tag"].as_str())
+        .collect();
+    assert_eq!(
+        tags,
+        [
+            "rust main.traced",
+            "rust main.justified",
+            "rust main.exported",
+            "rust main.main",
+            "rust main.sub.helper",
+            "rust main.generated",
+        ]
+    );
+    assert_eq!(merged["data"][3]["refs"][0], "req example.main");
+}
+
+#[test]
+fn append_conflicts_with_out() {
+    let (output, _) = run_case("functions", &["--append", "appended.lobster"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+}
+
+#[test]
+fn golden_extra_roots() {
+    check_case("extra_roots", &["--bin", "tool", "--build-script"]);
+}
+
+#[test]
+fn golden_roots_manifest() {
+    let roots = fixture_dir("roots").join("roots.toml");
+    check_case("roots", &["--roots", roots.to_str().unwrap()]);
+}
+
+#[test]
+fn roots_only_replaces_the_entry_file() {
+    let roots = fixture_dir("roots").join("roots.toml");
+    let (output, outfile) = run_case(
+        "roots",
+        &["--roots", roots.to_str().unwrap(), "--roots-only"],
+    );
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let tags: Vec<&str> = actual["data"]
+        .members()
+        .filter_map(|item| item["tag"].as_str())
+        .collect();
+    assert_eq!(
+        tags,
+        [
+            "rust gen.generate",
+            "rust gen.util.helper",
+            "rust checker.check"
+        ]
+    );
+}
+
+#[test]
+fn golden_all_bins() {
+    check_case("all_bins", &["--all-bins"]);
+}
+
+/// Recursively copy a directory.
+///
+/// ### Parameters
+/// * `from` - Directory to copy.
+/// * `to` - Destination directory, created if it does not exist.
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let path = entry.unwrap().path();
+        let target = to.join(path.file_name().unwrap());
+        if path.is_dir() {
+            copy_dir(&path, &target);
+        } else {
+            fs::copy(&path, &target).unwrap();
+        }
+    }
+}
+
+#[test]
+fn golden_gitignore() {
+    // A committed .gitignore would ignore the fixture files in the checkout, it is written into
+    // a copy of the fixture instead.
+    let fixture_copy = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("gitignore_fixture");
+    let _ = fs::remove_dir_all(&fixture_copy);
+    copy_dir(
+        &fixture_dir("gitignore").join("src"),
+        &fixture_copy.join("src"),
+    );
+    fs::write(
+        fixture_copy.join(".gitignore"),
+        "# Generated code and scratch files are not traced.\n/src/generated.rs\nscratch.rs\n",
+    )
+    .unwrap();
+    let outfile = fixture_copy.join("rust.lobster");
+    let output = Command::new(env!("CARGO_BIN_EXE_lobster-rust"))
+        .arg(fixture_copy.join("src"))
+        .arg(&outfile)
+        .args(["--respect-gitignore", "--all-bins"])
+        .output()
+        .expect("failed to run lobster-rust");
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let actual = normalize(&actual, &fixture_copy);
+    let expected = json::parse(
+        &fs::read_to_string(fixture_dir("gitignore").join("expected.lobster")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(actual, expected, "{}", actual.pretty(4));
+}
+
+#[test]
+fn serial_parsing_matches_parallel_parsing() {
+    let (serial, serial_outfile) = run_case("modules", &["--threads", "1"]);
+    let (parallel, parallel_outfile) = run_case("modules", &["--threads", "4"]);
+    assert!(serial.status.success(), "lobster-rust failed: {serial:?}");
+    assert!(
+        parallel.status.success(),
+        "lobster-rust failed: {parallel:?}"
+    );
+    assert_eq!(
+        fs::read_to_string(serial_outfile).unwrap(),
+        fs::read_to_string(parallel_outfile).unwrap()
+    );
+}
+
+#[test]
+fn list_files_prints_all_parsed_files() {
+    let fixture_dir = fixture_dir("submodules");
+    let (output, outfile) = run_case("submodules", &["--list-files"]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    assert!(!outfile.exists());
+
+    let listed: Vec<PathBuf> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| fs::canonicalize(line).unwrap())
+        .collect();
+    let expected: Vec<PathBuf> = ["src/main.rs", "src/sub.rs", "src/sub/nested.rs"]
+        .iter()
+        .map(|path| fs::canonicalize(fixture_dir.join(path)).unwrap())
+        .collect();
+    assert_eq!(listed, expected);
+}
+
+#[test]
+fn list_files_follows_included_files() {
+    let fixture_dir = fixture_dir("modules");
+    let (output, outfile) = run_case("modules", &["--list-files"]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    assert!(!outfile.exists());
+
+    let mut listed: Vec<PathBuf> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| fs::canonicalize(line).unwrap())
+        .collect();
+    listed.sort();
+    let mut expected: Vec<PathBuf> = ["src/main.rs", "src/sub.rs", "src/generated.rs"]
+        .iter()
+        .map(|path| fs::canonicalize(fixture_dir.join(path)).unwrap())
+        .collect();
+    expected.sort();
+    assert_eq!(listed, expected);
+}
+
+#[test]
+fn emit_files_lists_all_parsed_files() {
+    let (output, outfile) = run_case("submodules", &["--emit-files"]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    let document = json::parse(&fs::read_to_string(outfile).unwrap()).unwrap();
+    let files: Vec<&str> = document["files"]
+        .members()
+        .map(|file| file.as_str().unwrap())
+        .collect();
+    assert_eq!(files, ["src/main.rs", "src/sub.rs", "src/sub/nested.rs"]);
+}
+
+#[test]
+fn relative_to_git_root_emits_repository_paths() {
+    // The fixture is copied into a repository of its own, independent of the checkout.
+    let repo_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("git_root_repo");
+    fs::create_dir_all(repo_dir.join(".git")).unwrap();
+    fs::create_dir_all(repo_dir.join("src")).unwrap();
+    for file in ["src/main.rs", "src/sub.rs"] {
+        fs::copy(fixture_dir("git_root").join(file), repo_dir.join(file)).unwrap();
+    }
+    let outfile = repo_dir.join("rust.lobster");
+    let output = Command::new(env!("CARGO_BIN_EXE_lobster-rust"))
+        .arg(repo_dir.join("src"))
+        .arg(&outfile)
+        .arg("--relative-to-git-root")
+        .output()
+        .expect("failed to run lobster-rust");
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let expected =
+        json::parse(&fs::read_to_string(fixture_dir("git_root").join("expected.lobster")).unwrap())
+            .unwrap();
+    assert_eq!(actual, expected, "{}", actual.pretty(4));
+}
+
+#[test]
+fn relative_to_git_root_warns_without_repository() {
+    // The target directory is part of the checkout, the temporary directory of the system is not.
+    let run = RUN_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let dir =
+        std::env::temp_dir().join(format!("lobster_rust_no_git_{}_{run}", std::process::id()));
+    fs::create_dir_all(dir.join("src")).unwrap();
+    for file in ["src/main.rs", "src/sub.rs"] {
+        fs::copy(fixture_dir("git_root").join(file), dir.join(file)).unwrap();
+    }
+    let outfile = dir.join("rust.lobster");
+    let output = Command::new(env!("CARGO_BIN_EXE_lobster-rust"))
+        .arg(dir.join("src"))
+        .arg(&outfile)
+        .arg("--relative-to-git-root")
+        .output()
+        .expect("failed to run lobster-rust");
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("WARNING: No git repository found for"),
+        "{stderr}"
+    );
+
+    // The paths are emitted as given instead.
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let files: Vec<String> = actual["data"]
+        .members()
+        .map(|item| item["location"]["file"].to_string())
+        .collect();
+    let expected: Vec<String> = ["main.rs", "sub.rs"]
+        .map(|file| {
+            dir.join("src")
+                .join(file)
+                .display()
+                .to_string()
+                .replace('\\', "/")
+        })
+        .to_vec();
+    assert_eq!(files, expected);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn input_list_traces_listed_files_only() {
+    let src_dir = fixture_dir("input_list").join("src");
+    let input_list = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("input_list.txt");
+    fs::write(
+        &input_list,
+        format!(
+            "# Generated by the build system\n{}\n{}\n",
+            src_dir.join("main.rs").display(),
+            src_dir.join("extra/other.rs").display()
+        ),
+    )
+    .unwrap();
+    check_case(
+        "input_list",
+        &["--input-list", input_list.to_str().unwrap()],
+    );
+}
+
+#[test]
+fn golden_activity() {
+    check_case("activity", &["--activity"]);
+}
+
+#[test]
+fn golden_test_cases() {
+    check_case("test_cases", &["--activity"]);
+}
+
+#[test]
+fn activity_uses_additional_test_attributes() {
+    let (output, outfile) = run_case("activity", &["--activity", "--test-attrs", "rstest"]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let names: Vec<&str> = actual["data"]
+        .members()
+        .map(|item| item["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "main.tests.adds_numbers",
+            "main.tests.adds_asynchronously",
+            "main.tests.adds_with_fixture"
+        ]
+    );
+}
+
+#[test]
+fn parent_tag_reconstructs_hierarchy() {
+    let (output, outfile) = run_case("impls", &[]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let parent_tag = |name: &str| {
+        let item = actual["data"]
+            .members()
+            .find(|item| item["name"] == name)
+            .unwrap_or_else(|| panic!("{name} not found"));
+        item["parent_tag"].clone()
+    };
+    assert_eq!(parent_tag("main.Wrapper.value"), "rust main.Wrapper");
+    assert!(parent_tag("main.main").is_null());
+}
+
+#[test]
+fn scope_lists_all_enclosing_contexts() {
+    let (output, outfile) = run_case("scopes", &[]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let scope = |name: &str| -> Vec<String> {
+        let item = actual["data"]
+            .members()
+            .find(|item| item["name"] == name)
+            .unwrap_or_else(|| panic!("{name} not found"));
+        item["scope"]
+            .members()
+            .map(|context| context.as_str().unwrap_or_default().to_string())
+            .collect()
+    };
+    assert_eq!(scope("main.main"), ["main"]);
+    assert_eq!(scope("main.outer.inner.Item"), ["main", "outer", "inner"]);
+    assert_eq!(
+        scope("main.outer.inner.Item.method"),
+        ["main", "outer", "inner", "Item"]
+    );
+    assert_eq!(
+        scope("main.outer.inner.Item.method.local"),
+        ["main", "outer", "inner", "Item", "method"]
+    );
+}
+
+#[test]
+fn parent_tags_refer_to_emitted_items() {
+    for case in ["impl_types", "modules", "nested_items", "local_items"] {
+        let (output, outfile) = run_case(case, &[]);
+        assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+        let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+        let tags: Vec<&JsonValue> = actual["data"].members().map(|item| &item["tag"]).collect();
+        for item in actual["data"].members() {
+            assert!(
+                item["parent_tag"].is_null() || tags.contains(&&item["parent_tag"]),
+                "{case}: parent of {} is not emitted",
+                item["tag"]
+            );
+        }
+    }
+}
+
+#[test]
+fn kind_map_overrides_kind_strings() {
+    let (output, outfile) = run_case("structs", &["--kind-map", r#"{"Function": "function"}"#]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let kinds: Vec<&str> = actual["data"]
+        .members()
+        .map(|item| item["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, ["Struct", "Struct", "Struct", "function"]);
+}
+
+#[test]
+fn kind_map_rejects_unknown_kinds() {
+    let (output, _) = run_case("structs", &["--kind-map", r#"{"Method": "method"}"#]);
+    assert_eq!(output.status.code(), Some(2), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown kind"));
+}
+
+#[test]
+fn file_uri_emits_absolute_file_uris() {
+    let (output, outfile) = run_case("functions", &["--file-uri"]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    for item in actual["data"].members() {
+        let file = item["location"]["file"].as_str().unwrap();
+        let suffix = format!("/src/main.rs#L{}", item["location"]["line"]);
+        assert!(file.starts_with("file://"), "{file}");
+        assert!(file.ends_with(&suffix), "{file}");
+    }
+}
+
+/// Wait until the content of a file satisfies a condition.
+///
+/// ### Parameters
+/// * `path` - Path of the file.
+/// * `condition` - Condition on the file content.
+///
+/// ### Returns
+/// true if the condition was met before the timeout.
+fn wait_for_file(path: &Path, condition: impl Fn(&str) -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(20);
+    while Instant::now() < deadline {
+        if fs::read_to_string(path).is_ok_and(|content| condition(&content)) {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    false
+}
+
+#[test]
+fn watch_regenerates_output_on_change() {
+    let watch_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("watch");
+    let _ = fs::remove_dir_all(&watch_dir);
+    fs::create_dir_all(watch_dir.join("src")).unwrap();
+    let main_path = watch_dir.join("src/main.rs");
+    fs::copy(fixture_dir("functions").join("src/main.rs"), &main_path).unwrap();
+    let outfile = watch_dir.join("rust.lobster");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_lobster-rust"))
+        .arg(watch_dir.join("src"))
+        .arg(&outfile)
+        .arg("--watch")
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("failed to run lobster-rust");
+    let initial = wait_for_file(&outfile, |content| content.contains("main.main"));
+
+    let mut source = fs::read_to_string(&main_path).unwrap();
+    source.push_str("\nfn added() {\n    // lobster-trace: SWRS.added\n}\n");
+    fs::write(&main_path, source).unwrap();
+    let regenerated = wait_for_file(&outfile, |content| content.contains("main.added"));
+
+    child.kill().unwrap();
+    let _ = child.wait();
+    assert!(initial, "initial output was not written");
+    assert!(regenerated, "output was not regenerated after the change");
+}
+
+#[test]
+fn as_requirements_writes_requirements_document() {
+    let fixture_dir = fixture_dir("as_requirements");
+    let requirements_out =
+        PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("as_requirements_req.lobster");
+    check_case(
+        "as_requirements",
+        &[
+            "--as-requirements",
+            "trait",
+            "--requirements-out",
+            requirements_out.to_str().unwrap(),
+        ],
+    );
+
+    let actual = json::parse(&fs::read_to_string(&requirements_out).unwrap()).unwrap();
+    let expected = json::parse(
+        &fs::read_to_string(fixture_dir.join("expected_requirements.lobster")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(normalize(&actual, &fixture_dir), expected);
+}
+
+#[test]
+fn fields_of_requirement_structs_have_no_parent() {
+    let requirements_out = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("fields_req.lobster");
+    let (output, outfile) = run_case(
+        "fields",
+        &[
+            "--as-requirements",
+            "struct",
+            "--requirements-out",
+            requirements_out.to_str().unwrap(),
+        ],
+    );
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+
+    // The struct is emitted as requirement, it is no item of the implementation document.
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let field = actual["data"]
+        .members()
+        .find(|item| item["name"] == "main.Config.timeout")
+        .unwrap();
+    assert!(field["parent_tag"].is_null());
+    assert!(!actual["data"]
+        .members()
+        .any(|item| item["name"] == "main.Config"));
+}
+
+#[test]
+fn golden_manifest() {
+    check_case("manifest", &[]);
+}
+
+#[test]
+fn crate_metadata_is_omitted_without_manifest() {
+    let (output, outfile) = run_case("functions", &[]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let actual = json::parse(&fs::read_to_string(outfile).unwrap()).unwrap();
+    assert!(!actual.has_key("crate_name"));
+    assert!(!actual.has_key("crate_version"));
+}
+
+#[test]
+fn manifest_option_reads_the_given_manifest() {
+    let manifest = fixture_dir("manifest").join("Cargo.toml");
+    let (output, outfile) = run_case("functions", &["--manifest", manifest.to_str().unwrap()]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let actual = json::parse(&fs::read_to_string(outfile).unwrap()).unwrap();
+    assert_eq!(actual["crate_name"], "potato-farm");
+    assert_eq!(actual["crate_version"], "1.2.3");
+}
+
+#[test]
+fn unreadable_manifest_is_reported() {
+    let manifest = fixture_dir("functions").join("Cargo.toml");
+    let (output, _) = run_case("functions", &["--manifest", manifest.to_str().unwrap()]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Manifest: "));
+}
+
+#[test]
+fn golden_ambiguous_module() {
+    check_case("ambiguous_module", &[]);
+}
+
+#[test]
+fn unnamed_module_is_reported() {
+    let (output, outfile) = run_case("unnamed_module", &[]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let warning = stderr
+        .lines()
+        .find(|line| line.starts_with("WARNING: Module without name in "))
+        .unwrap_or_else(|| panic!("no warning in {stderr}"));
+    assert!(warning.ends_with("main.rs\" @3,1"), "{warning}");
+
+    // The items following the unnamed module are traced nonetheless.
+    let actual = json::parse(&fs::read_to_string(&outfile).unwrap()).unwrap();
+    let names: Vec<&str> = actual["data"]
+        .members()
+        .filter_map(|item| item["name"].as_str())
+        .collect();
+    assert!(names.contains(&"main.named.inner"));
+    assert!(names.contains(&"main.main"));
+}
+
+#[test]
+fn golden_module_casing() {
+    check_case("module_casing", &["--case-insensitive-modules"]);
+    let (output, _) = run_case("module_casing", &["--case-insensitive-modules"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("WARNING: Module Storage.rs resolved to"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("with different casing"), "{stderr}");
+}
+
+#[test]
+fn module_casing_is_not_resolved_by_default() {
+    let (output, _) = run_case("module_casing", &[]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Module Storage declared in"), "{stderr}");
+}
+
+#[test]
+fn ambiguous_module_is_reported() {
+    let (output, _) = run_case("ambiguous_module", &[]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("WARNING: Ambiguous module"));
+}
+
+#[test]
+fn golden_nested_items() {
+    check_case("nested_items", &[]);
+}
+
+#[test]
+fn golden_local_impl() {
+    check_case("local_impl", &[]);
+}
+
+#[test]
+fn golden_local_items() {
+    check_case("local_items", &[]);
+}
+
+#[test]
+fn unknown_requirements_are_reported() {
+    let requirements = fixture_dir("requirements").join("requirements.txt");
+    let (output, _) = run_case(
+        "requirements",
+        &["--requirements", requirements.to_str().unwrap()],
+    );
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(
+        "WARNING: Unknown requirement req SWRS.missing referenced by Function main.dangling in main @5,1"
+    ));
+    assert!(!stderr.contains("SWRS.known"));
+}
+
+#[test]
+fn strict_refs_fails_on_unknown_requirements() {
+    let requirements = fixture_dir("requirements").join("requirements.lobster");
+    let (output, _) = run_case(
+        "requirements",
+        &[
+            "--requirements",
+            requirements.to_str().unwrap(),
+            "--strict-refs",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("ERROR: Unknown requirement req SWRS.missing"));
+    assert!(!stderr.contains("SWRS.known"));
+}
+
+#[test]
+fn diff_reports_changed_items() {
+    let before = fixture_dir("diff").join("before.lobster");
+    let (output, _) = run_case("diff", &["--diff", before.to_str().unwrap()]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("+ rust main.added\n"));
+    assert!(stderr.contains("- rust main.removed\n"));
+    assert!(stderr.contains("~ rust main.retraced: [req SWRS.old] -> [req SWRS.retraced]\n"));
+    assert!(stderr.contains("1 added, 1 removed, 1 changed"));
+    assert!(!stderr.contains("main.kept"));
+}
+
+#[test]
+fn diff_out_writes_report_file() {
+    let before = fixture_dir("diff").join("before.lobster");
+    let diff_out = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("diff_report.txt");
+    let (output, _) = run_case(
+        "diff",
+        &[
+            "--diff",
+            before.to_str().unwrap(),
+            "--diff-out",
+            diff_out.to_str().unwrap(),
+        ],
+    );
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    assert_eq!(
+        fs::read_to_string(&diff_out).unwrap(),
+        "+ rust main.added\n\
+         - rust main.removed\n\
+         ~ rust main.retraced: [req SWRS.old] -> [req SWRS.retraced]\n\
+         1 added, 1 removed, 1 changed\n"
+    );
+}
+
+#[test]
+fn golden_strip_prefix() {
+    check_case("strip_prefix", &["--strip-prefix", "CompanyProj."]);
+}
+
+#[test]
+fn format_yaml_writes_yaml_document() {
+    let (output, outfile) = run_case("functions", &["--format", "yaml"]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    let content = fs::read_to_string(&outfile).unwrap();
+    assert!(
+        content.starts_with("data:\n  - tag: \"rust main."),
+        "{content}"
+    );
+    assert!(content.contains("\ngenerator: \"lobster-rust\"\n"));
+    assert!(content.ends_with("version: 3\n"));
+}
+
+#[test]
+fn golden_generic_impls() {
+    check_case("generic_impls", &[]);
+}
+
+#[test]
+fn ref_histogram_counts_referencing_items() {
+    let (output, outfile) = run_case("ref_histogram", &["--ref-histogram"]);
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "3\tSWRS.common\n1\tSWRS.single\n"
+    );
+    assert!(!outfile.exists(), "output was written");
+}
+
+#[test]
+fn ref_histogram_counts_only_written_items() {
+    let (output, _) = run_case(
+        "ref_histogram",
+        &["--ref-histogram", "--skip-kinds", "struct"],
+    );
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "2\tSWRS.common\n1\tSWRS.single\n"
+    );
+}
+
+#[test]
+fn ref_histogram_lists_unreferenced_requirements() {
+    let requirements = fixture_dir("ref_histogram").join("requirements.txt");
+    let (output, _) = run_case(
+        "ref_histogram",
+        &[
+            "--ref-histogram",
+            "--requirements",
+            requirements.to_str().unwrap(),
+        ],
+    );
+    assert!(output.status.success(), "lobster-rust failed: {output:?}");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "3\tSWRS.common\n1\tSWRS.single\n0\tSWRS.unused\n"
+    );
+}
+
+#[test]
+fn golden_path_parent() {
+    check_case("path_parent", &[]);
+    let (output, _) = run_case("path_parent", &[]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Module missing declared in"), "{stderr}");
+    assert!(
+        !stderr.contains("outside of the project directory"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn golden_macro_bodies() {
+    check_case("macro_bodies", &["--parse-macro-bodies"]);
+}
+
+#[test]
+fn golden_file_as_context() {
+    check_case("file_as_context", &["--file-as-context"]);
+}
+
+#[test]
+fn golden_ref_impl() {
+    check_case("ref_impl", &[]);
+}
+
+#[test]
+fn golden_no_context() {
+    check_case("no_context", &["--no-context"]);
+}
+
+#[test]
+fn golden_colliding_parents() {
+    check_case("colliding_parents", &["--no-context"]);
+}
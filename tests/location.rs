@@ -0,0 +1,88 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Tests for the conversion of FileReferences to file URIs and location objects.
+
+use json::JsonValue;
+use lobster_rust::location::FileReference;
+
+/// Build a FileReference to the given path and line.
+fn reference(path: &str, line: Option<usize>) -> FileReference {
+    FileReference::new(path.to_string(), line, Some(1))
+}
+
+#[test]
+fn unix_path_is_converted_to_file_uri() {
+    assert_eq!(
+        reference("/home/dev/crate/src/main.rs", Some(12)).to_file_uri(),
+        "file:///home/dev/crate/src/main.rs#L12"
+    );
+}
+
+#[test]
+fn windows_path_is_converted_to_file_uri() {
+    assert_eq!(
+        reference(r"C:\Users\dev\crate\src\main.rs", Some(3)).to_file_uri(),
+        "file:///C:/Users/dev/crate/src/main.rs#L3"
+    );
+    assert_eq!(
+        reference(r"\\?\C:\Users\dev\crate\src\main.rs", Some(3)).to_file_uri(),
+        "file:///C:/Users/dev/crate/src/main.rs#L3"
+    );
+    assert_eq!(
+        reference(r"\\?\UNC\server\share\src\main.rs", Some(3)).to_file_uri(),
+        "file://server/share/src/main.rs#L3"
+    );
+}
+
+#[test]
+fn relative_path_is_resolved_before_conversion() {
+    let absolute = std::env::current_dir()
+        .unwrap()
+        .join("relative")
+        .join("main.rs");
+    assert_eq!(
+        reference("relative/main.rs", Some(1)).to_file_uri(),
+        reference(&absolute.display().to_string(), Some(1)).to_file_uri()
+    );
+}
+
+#[test]
+fn special_characters_are_percent_encoded() {
+    assert_eq!(
+        reference("/home/dev/my crate/src/#1/größe.rs", None).to_file_uri(),
+        "file:///home/dev/my%20crate/src/%231/gr%C3%B6%C3%9Fe.rs"
+    );
+}
+
+#[test]
+fn backslash_paths_are_emitted_with_forward_slashes() {
+    let location = JsonValue::from(&reference(r"crate\src\sub\nested.rs", Some(2)));
+    assert_eq!(location["file"], "crate/src/sub/nested.rs");
+}
@@ -0,0 +1,103 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Tests for the serialization of output documents.
+
+use json::object;
+use lobster_rust::output::{write_document, DocumentFormat};
+use std::io::{self, BufWriter, Write};
+
+/// Writer accepting a limited number of bytes, failing on any further write.
+struct FailingWriter {
+    /// Number of bytes that are still accepted.
+    capacity: usize,
+    /// Bytes written so far.
+    written: Vec<u8>,
+}
+
+impl Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if 0 == self.capacity {
+            return Err(io::Error::other("disk full"));
+        }
+        let accepted = buf.len().min(self.capacity);
+        self.capacity -= accepted;
+        self.written.extend_from_slice(&buf[..accepted]);
+        Ok(accepted)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Build a failing writer accepting the given number of bytes.
+fn failing_writer(capacity: usize) -> FailingWriter {
+    FailingWriter {
+        capacity,
+        written: Vec::new(),
+    }
+}
+
+/// Build a small lobster document.
+fn document() -> json::JsonValue {
+    object! {
+        "data": [{ "tag": "rust main.main", "refs": ["req SWRS.main"] }],
+        "generator": "lobster-rust",
+        "schema": "lobster-imp-trace",
+        "version": 3
+    }
+}
+
+#[test]
+fn complete_document_is_written() {
+    for format in [DocumentFormat::Json, DocumentFormat::Yaml] {
+        let mut writer = failing_writer(usize::MAX);
+        write_document(&mut writer, &document(), format).unwrap();
+        assert!(!writer.written.is_empty());
+    }
+}
+
+#[test]
+fn write_error_mid_document_surfaces() {
+    for format in [DocumentFormat::Json, DocumentFormat::Yaml] {
+        let mut writer = failing_writer(10);
+        let error = write_document(&mut writer, &document(), format).unwrap_err();
+        assert_eq!(error.to_string(), "disk full");
+        assert_eq!(writer.written.len(), 10);
+    }
+}
+
+#[test]
+fn write_error_of_buffered_writer_surfaces_on_flush() {
+    // The buffer holds the whole document, so the inner writer is only written to on flush.
+    let mut writer = BufWriter::with_capacity(4096, failing_writer(10));
+    let error = write_document(&mut writer, &document(), DocumentFormat::Json).unwrap_err();
+    assert_eq!(error.to_string(), "disk full");
+}
@@ -0,0 +1,64 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Tests for the comparison of lobster traces.
+
+use lobster_rust::trace_diff::{trace_refs, TraceDiff};
+
+/// Build the references by tag of a trace given as lobster data array.
+fn trace(data: &str) -> lobster_rust::trace_diff::TraceRefs {
+    let data = json::parse(data).unwrap();
+    trace_refs(data.members())
+}
+
+#[test]
+fn equal_traces_have_no_differences() {
+    let old = trace(r#"[{"tag": "rust main.a", "refs": ["req A", "req B"]}]"#);
+    let new = trace(r#"[{"tag": "rust main.a", "refs": ["req B", "req A"]}]"#);
+    let diff = TraceDiff::new(&old, &new);
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "0 added, 0 removed, 0 changed");
+}
+
+#[test]
+fn differences_are_sorted_by_tag() {
+    let old =
+        trace(r#"[{"tag": "rust main.c", "refs": []}, {"tag": "rust main.a", "refs": ["req A"]}]"#);
+    let new = trace(
+        r#"[{"tag": "rust main.d", "refs": []}, {"tag": "rust main.b", "refs": []},
+            {"tag": "rust main.a", "refs": []}]"#,
+    );
+    let diff = TraceDiff::new(&old, &new);
+    assert_eq!(diff.added, ["rust main.b", "rust main.d"]);
+    assert_eq!(diff.removed, ["rust main.c"]);
+    assert_eq!(
+        diff.changed,
+        [("rust main.a".to_string(), vec!["req A".to_string()], vec![])]
+    );
+}
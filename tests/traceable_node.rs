@@ -0,0 +1,304 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Tests for the RustTraceableNode tree produced by the RustVisitor.
+
+use lobster_rust::traceable_node::{NodeKind, OutputOptions, RustTraceableNode};
+use lobster_rust::utils::context::Context;
+use lobster_rust::visitor::{LocateAt, RustVisitor, VisitorExtension, VisitorOptions};
+use ra_ap_syntax::{SyntaxKind, SyntaxNode};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Get the path of the main.rs of a fixture.
+fn fixture_main(fixture: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(fixture)
+        .join("src/main.rs")
+}
+
+/// Parse the main.rs of a fixture and return the root node of the entry file.
+fn parse_fixture(fixture: &str) -> RustTraceableNode {
+    parse_fixture_with(fixture, VisitorOptions::default())
+}
+
+/// Parse the main.rs of a fixture with the given options and return the root node of the entry
+/// file.
+fn parse_fixture_with(fixture: &str, options: VisitorOptions) -> RustTraceableNode {
+    let filepath = fixture_main(fixture);
+    let mut visitor = RustVisitor::new(filepath, Context::Empty, options);
+    visitor.parse_file();
+    visitor.get_traceable_nodes().remove(0)
+}
+
+#[test]
+fn find_by_name_finds_top_level_function() {
+    let root = parse_fixture("find_by_name");
+    let node = root.find_by_name("main.main").expect("main.main not found");
+    assert_eq!(node.kind, NodeKind::Function);
+}
+
+#[test]
+fn find_by_name_finds_nested_nodes() {
+    let root = parse_fixture("find_by_name");
+    let node = root
+        .find_by_name("main.inner.Foo.bar")
+        .expect("main.inner.Foo.bar not found");
+    assert_eq!(node.kind, NodeKind::Function);
+    let node = root
+        .find_by_name("main.inner.Foo")
+        .expect("main.inner.Foo not found");
+    assert_eq!(node.kind, NodeKind::Struct);
+}
+
+#[test]
+fn find_by_name_returns_none_for_unknown_name() {
+    let root = parse_fixture("find_by_name");
+    assert!(root.find_by_name("main.bar").is_none());
+    assert!(root.find_by_name("main.Foo.bar").is_none());
+}
+
+#[test]
+fn utf8_bom_does_not_shift_positions() {
+    let with_bom = parse_fixture("bom");
+    let without_bom = parse_fixture("no_bom");
+    for name in ["main.first", "main.main"] {
+        let expected = &without_bom.find_by_name(name).unwrap().location;
+        let actual = &with_bom.find_by_name(name).unwrap().location;
+        assert_eq!(actual, expected, "position of {name} differs");
+    }
+}
+
+#[test]
+fn malformed_items_do_not_abort_parsing() {
+    let root = parse_fixture("malformed");
+    assert_eq!(root.kind, NodeKind::Source);
+    assert!(root.find_by_name("main.main").is_some());
+}
+
+#[test]
+fn stray_keywords_do_not_abort_parsing() {
+    let root = parse_fixture("stray_keywords");
+    assert_eq!(root.kind, NodeKind::Source);
+    assert!(root.find_by_name("main.stray").is_some());
+    assert!(root.find_by_name("main.main").is_some());
+}
+
+#[test]
+fn deeply_nested_expressions_do_not_overflow_the_stack() {
+    let root = parse_fixture("deep_nesting");
+    let node = root.find_by_name("main.deep").expect("main.deep not found");
+    assert_eq!(node.refs, ["req SWRS.deep"]);
+    assert!(root.find_by_name("main.main").is_some());
+}
+
+#[test]
+fn unnamed_module_is_skipped() {
+    let root = parse_fixture("unnamed_module");
+    assert!(root.find_by_name("main.orphan").is_some());
+    assert!(root.find_by_name("main.named.inner").is_some());
+    assert!(root.find_by_name("main.main").is_some());
+}
+
+#[test]
+fn nocheck_comment_adds_no_justification() {
+    let root = parse_fixture("suppression");
+    let node = root.find_by_name("main.unchecked").unwrap();
+    assert!(node.nocheck);
+    assert!(!node.is_traced());
+    let node = root.find_by_name("main.excluded").unwrap();
+    assert!(!node.nocheck);
+    assert_eq!(node.just_up, ["UNTRACED"]);
+}
+
+#[test]
+fn impl_context_nodes_are_named_after_the_impl_header() {
+    let root = parse_fixture("impls");
+    let names: Vec<&str> = root
+        .children
+        .iter()
+        .filter(|node| NodeKind::Context == node.kind)
+        .map(|node| node.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "impl Wrapper",
+            "impl Describe for T",
+            "impl !Send for Wrapper"
+        ]
+    );
+    assert!(root.find_by_name("main.Wrapper.value").is_some());
+    assert!(root.find_by_name("main.Describe.name").is_some());
+}
+
+#[test]
+fn set_filename_normalizes_backslashes() {
+    let mut root = parse_fixture("functions");
+    root.set_filename(r"crate\src\main.rs");
+    let items = root.to_lobster(&OutputOptions::default());
+    assert!(!items.is_empty());
+    for item in items.iter() {
+        assert_eq!(item["location"]["file"], "crate/src/main.rs");
+    }
+}
+
+/// Check that the locations of all functions, structs and macros point to their keyword.
+fn assert_keyword_locations(node: &RustTraceableNode, lines: &[&str]) {
+    let keyword = match node.kind {
+        NodeKind::Function => Some("fn"),
+        NodeKind::Struct => Some("struct"),
+        NodeKind::Macro => Some("macro"),
+        _ => None,
+    };
+    if let Some(keyword) = keyword {
+        let line = lines[node.location.line.unwrap() - 1];
+        let column = node.location.column.unwrap();
+        assert!(
+            line.as_bytes()[column - 1..].starts_with(keyword.as_bytes()),
+            "{} is not located at its keyword: {line:?} @{column}",
+            node.name
+        );
+    }
+    for child in node.children.iter() {
+        assert_keyword_locations(child, lines);
+    }
+}
+
+#[test]
+fn locations_point_to_item_keywords() {
+    for fixture in [
+        "functions",
+        "impls",
+        "structs",
+        "columns",
+        "dense",
+        "shebang",
+        "nested_items",
+        "macro_def",
+    ] {
+        let text = fs::read_to_string(fixture_main(fixture)).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_keyword_locations(&parse_fixture(fixture), &lines);
+    }
+}
+
+#[test]
+fn locations_point_to_item_names_if_configured() {
+    let text = fs::read_to_string(fixture_main("functions")).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    let at_keyword = parse_fixture("functions");
+    let at_name = parse_fixture_with(
+        "functions",
+        VisitorOptions {
+            locate_at: LocateAt::Name,
+            ..VisitorOptions::default()
+        },
+    );
+    for node in at_name.children.iter() {
+        let keyword_location = &at_keyword.find_by_name(&node.name).unwrap().location;
+        let line = lines[node.location.line.unwrap() - 1];
+        let column = node.location.column.unwrap();
+        let ident = node.name.rsplit('.').next().unwrap();
+        assert!(
+            line.as_bytes()[column - 1..].starts_with(ident.as_bytes()),
+            "{} is not located at its name: {line:?} @{column}",
+            node.name
+        );
+        // The name follows the keyword on the same line.
+        assert_eq!(node.location.line, keyword_location.line);
+        assert_eq!(column, keyword_location.column.unwrap() + "fn ".len());
+    }
+}
+
+#[test]
+fn root_nodes_can_be_accessed_repeatedly() {
+    let filepath =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/submodules/src/main.rs");
+    let mut visitor = RustVisitor::new(filepath, Context::Empty, VisitorOptions::default());
+    visitor.parse_file();
+
+    let names = |nodes: Vec<&RustTraceableNode>| -> Vec<String> {
+        nodes.iter().map(|n| n.name.clone()).collect()
+    };
+    let first = names(visitor.root_nodes());
+    let second = names(visitor.root_nodes());
+    assert_eq!(first, ["main", "sub", "nested"]);
+    assert_eq!(first, second);
+
+    let consumed: Vec<String> = visitor
+        .get_traceable_nodes()
+        .into_iter()
+        .map(|n| n.name)
+        .collect();
+    assert_eq!(consumed, first);
+}
+
+/// Example extension counting the entered `fn` nodes and annotating the functions.
+#[derive(Debug, Default)]
+struct FnCounter {
+    count: AtomicUsize,
+}
+
+impl VisitorExtension for FnCounter {
+    fn node_enter(&self, node: &SyntaxNode, current: Option<&mut RustTraceableNode>) {
+        if SyntaxKind::FN == node.kind() {
+            let _ = self.count.fetch_add(1, Ordering::SeqCst);
+            if let Some(fn_node) = current {
+                fn_node.refs.push("req SWRS.counted".to_string());
+            }
+        }
+    }
+}
+
+#[test]
+fn extensions_are_invoked_for_all_files() {
+    let counter = Arc::new(FnCounter::default());
+    let filepath =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/submodules/src/main.rs");
+    let options = VisitorOptions {
+        extensions: vec![counter.clone()],
+        ..VisitorOptions::default()
+    };
+    let mut visitor = RustVisitor::new(filepath, Context::Empty, options);
+    visitor.parse_file();
+
+    assert_eq!(counter.count.load(Ordering::SeqCst), 3);
+    let roots = visitor.root_nodes();
+    for (root, name) in roots
+        .iter()
+        .zip(["main.main", "main.sub.helper", "main.sub.nested.deep"])
+    {
+        let node = root.find_by_name(name).expect(name);
+        assert_eq!(node.refs, ["req SWRS.counted"]);
+    }
+}
@@ -0,0 +1,99 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Tests for the validation of emitted items.
+
+use lobster_rust::traceable_node::{OutputOptions, RustTraceableNode};
+use lobster_rust::utils::context::Context;
+use lobster_rust::validation::unlocated_item_warnings;
+use lobster_rust::visitor::{RustVisitor, VisitorExtension, VisitorOptions};
+use ra_ap_syntax::{SyntaxKind, SyntaxNode};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Extension dropping the location of every function on exit, like a location tracking gap.
+#[derive(Debug)]
+struct LocationGap;
+
+impl VisitorExtension for LocationGap {
+    fn node_exit(&self, node: &SyntaxNode, current: Option<&mut RustTraceableNode>) {
+        if let Some(fn_node) = current.filter(|_| SyntaxKind::FN == node.kind()) {
+            fn_node.location.line = None;
+            fn_node.location.column = None;
+        }
+    }
+}
+
+#[test]
+fn item_with_refs_and_without_line_is_reported() {
+    let data = json::parse(
+        r#"[
+            {"name": "main.lost", "refs": ["req SWRS.lost"],
+             "location": {"kind": "file", "file": "main", "line": null, "column": null}},
+            {"name": "main.found", "refs": ["req SWRS.found"],
+             "location": {"kind": "file", "file": "main", "line": 3, "column": 1}},
+            {"name": "main.untraced", "refs": [],
+             "location": {"kind": "file", "file": "main", "line": null, "column": null}}
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(
+        unlocated_item_warnings(data.members()),
+        ["WARNING: Item main.lost has references but no location in main."]
+    );
+}
+
+#[test]
+fn parsed_item_without_location_is_reported() {
+    let filepath =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/unlocated/src/main.rs");
+    let options = VisitorOptions {
+        extensions: vec![Arc::new(LocationGap)],
+        ..VisitorOptions::default()
+    };
+    let mut visitor = RustVisitor::new(filepath, Context::Empty, options);
+    visitor.parse_file();
+    let items: Vec<json::JsonValue> = visitor
+        .get_traceable_nodes()
+        .iter()
+        .flat_map(|root| root.to_lobster(&OutputOptions::default()))
+        .collect();
+
+    // Both functions are emitted without line, only the traced one is reported.
+    let names: Vec<String> = items
+        .iter()
+        .filter(|item| item["location"]["line"].is_null())
+        .map(|item| item["name"].to_string())
+        .collect();
+    assert_eq!(names, ["main.lost", "main.main"]);
+    assert_eq!(
+        unlocated_item_warnings(&items),
+        ["WARNING: Item main.lost has references but no location in main."]
+    );
+}
@@ -0,0 +1,105 @@
+// BSD 3-Clause License
+//
+// Copyright (c) 2025, NewTec GmbH
+//
+// Redistribution and use in source and binary forms, with or without
+// modification, are permitted provided that the following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions
+//    and the following disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of
+//    conditions and the following disclaimer in the documentation and/or other materials provided
+//    with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to
+//    endorse or promote products derived from this software without specific prior written
+//    permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS"
+// AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+// IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICU5LAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+// FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+// DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+// CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+// OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+// OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Tests for the YAML serialization of lobster documents.
+
+use json::{object::Object, JsonValue};
+use lobster_rust::yaml::to_yaml;
+use std::fs;
+use std::path::PathBuf;
+use yaml_rust2::{Yaml, YamlLoader};
+
+/// Convert a parsed YAML document to the equivalent JsonValue.
+fn yaml_to_json(yaml: &Yaml) -> JsonValue {
+    match yaml {
+        Yaml::Null => JsonValue::Null,
+        Yaml::Boolean(value) => JsonValue::Boolean(*value),
+        Yaml::Integer(value) => JsonValue::from(*value),
+        Yaml::Real(value) => JsonValue::from(value.parse::<f64>().unwrap()),
+        Yaml::String(value) => JsonValue::String(value.clone()),
+        Yaml::Array(items) => JsonValue::Array(items.iter().map(yaml_to_json).collect()),
+        Yaml::Hash(hash) => {
+            let mut object = Object::new();
+            for (key, value) in hash.iter() {
+                object.insert(key.as_str().unwrap(), yaml_to_json(value));
+            }
+            JsonValue::Object(object)
+        }
+        _ => panic!("unexpected YAML value {yaml:?}"),
+    }
+}
+
+/// Serialize a JsonValue to YAML and parse it back.
+fn round_trip(value: &JsonValue) -> JsonValue {
+    let yaml = to_yaml(value);
+    let documents = YamlLoader::load_from_str(&yaml).expect("invalid YAML");
+    assert_eq!(documents.len(), 1, "expected one document:\n{yaml}");
+    yaml_to_json(&documents[0])
+}
+
+#[test]
+fn expected_outputs_survive_round_trip() {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut checked = 0;
+    for entry in fs::read_dir(fixtures).unwrap() {
+        let expected = entry.unwrap().path().join("expected.lobster");
+        let Ok(content) = fs::read_to_string(&expected) else {
+            continue;
+        };
+        let document = json::parse(&content).unwrap();
+        assert_eq!(round_trip(&document), document, "{expected:?}");
+        checked += 1;
+    }
+    assert!(checked > 0, "no expected outputs found");
+}
+
+#[test]
+fn scalars_keep_their_type() {
+    let document = json::object! {
+        "$schema": "https://example.com/schema.json",
+        "null": "null",
+        "strings": ["true", "1.5", "", "- not a list", "a: b", "quote \" and\nnewline", "#hash"],
+        "values": [null, true, false, 3, 1.5],
+        "empty": {"array": [], "object": {}},
+        "nested": [[1, [2]], [{"a": {"b": []}}]]
+    };
+    assert_eq!(round_trip(&document), document);
+}
+
+#[test]
+fn items_start_after_the_dash() {
+    let document = json::object! {
+        "data": [{"tag": "rust main.main", "refs": ["req A"]}],
+        "version": 3
+    };
+    assert_eq!(
+        to_yaml(&document),
+        "data:\n  - tag: \"rust main.main\"\n    refs:\n      - \"req A\"\nversion: 3\n"
+    );
+}